@@ -0,0 +1,238 @@
+use crate::khmer;
+use crate::lao;
+use crate::line_break_class;
+#[cfg(feature = "lstm")]
+use crate::lstm;
+use crate::myanmar;
+use crate::thai;
+use crate::LineBreakClass;
+use std::char::{decode_utf16, REPLACEMENT_CHARACTER};
+use std::sync::Arc;
+
+/// Scripts of complex language text (SA), which is broken by a segmenter of each script
+/// instead of the pair rules.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ComplexScript {
+    /// Thai, which is broken into words by an LSTM model by default. The model is in the `lstm`
+    /// feature, and Thai text isn't broken inside without it.
+    Thai,
+    /// Lao, which is broken into words by the Lao word list of ICU by default. The word list is
+    /// in the `dictionaries` feature, and Lao text is broken between syllables without it and
+    /// out of the words of the list.
+    Lao,
+    /// Khmer, which is broken into words by the Khmer word list of ICU by default. The word list
+    /// is in the `dictionaries` feature, and Khmer text is broken between orthographic clusters
+    /// without it and out of the words of the list.
+    Khmer,
+    /// Myanmar, which is broken between syllables by default. There is no Burmese model or
+    /// word list in this crate, so words are only found with a dictionary or another segmenter.
+    Myanmar,
+}
+
+/// A segmenter of complex language text, such as a platform one or another model, which is
+/// used instead of the built-in segmenter of a script by `LineBreakOptions::complex_segmenter`.
+///
+/// ```rust
+/// use std::sync::Arc;
+/// use uax14_rs::{ComplexScript, ComplexSegmenter, LineBreakIterator, LineBreakOptions};
+///
+/// // Break Thai text between every two characters.
+/// struct CharSegmenter;
+///
+/// impl ComplexSegmenter for CharSegmenter {
+///     fn segment_utf16(&self, input: &[u16]) -> Vec<usize> {
+///         (1..input.len()).collect()
+///     }
+/// }
+///
+/// let options = LineBreakOptions::new().complex_segmenter(ComplexScript::Thai, Arc::new(CharSegmenter));
+/// let breaks: Vec<usize> = LineBreakIterator::new_with_options("ไทย", &options).collect();
+/// assert_eq!(breaks, [3, 6, 9]);
+/// ```
+pub trait ComplexSegmenter: Send + Sync {
+    /// Return the break opportunities inside `input`, a run of complex language text of one
+    /// script, as UTF-16 offsets in ascending order. The start and the end of the run aren't
+    /// included; they are decided by the pair rules.
+    fn segment_utf16(&self, input: &[u16]) -> Vec<usize>;
+}
+
+// The segmenters chosen for scripts instead of the built-in ones.
+#[derive(Clone, Default)]
+pub(crate) struct ComplexSegmenters {
+    segmenters: Vec<(ComplexScript, Arc<dyn ComplexSegmenter>)>,
+}
+
+impl ComplexSegmenters {
+    pub(crate) fn set_segmenter(
+        &mut self,
+        script: ComplexScript,
+        segmenter: Arc<dyn ComplexSegmenter>,
+    ) {
+        self.segmenters.retain(|(s, _)| *s != script);
+        self.segmenters.push((script, segmenter));
+    }
+
+    fn get_segmenter(&self, script: ComplexScript) -> Option<&dyn ComplexSegmenter> {
+        self.segmenters
+            .iter()
+            .find(|(s, _)| *s == script)
+            .map(|(_, segmenter)| &**segmenter)
+    }
+}
+
+pub(crate) fn get_complex_script(codepoint: u32) -> Option<ComplexScript> {
+    match codepoint {
+        0xe01..=0xe7f => Some(ComplexScript::Thai),
+        // Lao, Khmer and Myanmar digits are NU, so only SA characters are segmented.
+        0xe80..=0xeff if is_complex_context(codepoint) => Some(ComplexScript::Lao),
+        0x1780..=0x17ff if is_complex_context(codepoint) => Some(ComplexScript::Khmer),
+        0x1000..=0x109f | 0xa9e0..=0xa9ff | 0xaa60..=0xaa7f if is_complex_context(codepoint) => {
+            Some(ComplexScript::Myanmar)
+        }
+        _ => None,
+    }
+}
+
+fn is_complex_context(codepoint: u32) -> bool {
+    match core::char::from_u32(codepoint) {
+        Some(c) => line_break_class(c) == LineBreakClass::ComplexContext,
+        None => false,
+    }
+}
+
+// Whether the end of a run of complex language text that ends with `codepoint` is a break
+// whatever follows it. The built-in Thai segmenter has always broken there, so it still does;
+// the end of the other runs is decided by the pair rules like any other position.
+pub(crate) fn is_break_after_run(codepoint: u32, segmenters: &ComplexSegmenters) -> bool {
+    get_complex_script(codepoint) == Some(ComplexScript::Thai)
+        && segmenters.get_segmenter(ComplexScript::Thai).is_none()
+}
+
+// Whether a break at `offset` doesn't separate marks or subscripts from their base character,
+// or a preposed vowel from the consonant after it. Each script decides it by its own rules.
+pub(crate) fn is_cluster_boundary(input: &[u16], offset: usize) -> bool {
+    if offset == 0 || offset >= input.len() {
+        return true;
+    }
+    let script = get_complex_script(input[offset] as u32);
+    if script != get_complex_script(input[offset - 1] as u32) {
+        return true;
+    }
+    match script {
+        Some(ComplexScript::Thai) => thai::is_cluster_boundary(input, offset),
+        Some(ComplexScript::Lao) => lao::is_cluster_boundary(input, offset),
+        Some(ComplexScript::Khmer) => khmer::is_cluster_boundary(input, offset),
+        Some(ComplexScript::Myanmar) => myanmar::is_cluster_boundary(input, offset),
+        None => true,
+    }
+}
+
+fn get_builtin_line_break_utf16(script: ComplexScript, input: &[u16]) -> Option<Vec<usize>> {
+    match script {
+        #[cfg(feature = "lstm")]
+        ComplexScript::Thai => lstm::get_line_break_utf16(input),
+        #[cfg(not(feature = "lstm"))]
+        ComplexScript::Thai => None,
+        ComplexScript::Lao => lao::get_line_break_utf16(input),
+        ComplexScript::Khmer => khmer::get_line_break_utf16(input),
+        ComplexScript::Myanmar => myanmar::get_line_break_utf16(input),
+    }
+}
+
+// Append the breaks inside `input[start..end]` that is in `script`.
+fn push_script_breaks(
+    result: &mut Vec<usize>,
+    segmenters: &ComplexSegmenters,
+    script: Option<ComplexScript>,
+    input: &[u16],
+    start: usize,
+    end: usize,
+) {
+    let input = &input[start..end];
+    let breaks = match script {
+        Some(script) => match segmenters.get_segmenter(script) {
+            Some(segmenter) => {
+                // Drop the offsets out of the run, which the iterators can't handle.
+                let mut breaks: Vec<usize> = segmenter
+                    .segment_utf16(input)
+                    .into_iter()
+                    .filter(|&b| b > 0 && b < input.len())
+                    .collect();
+                breaks.sort_unstable();
+                breaks.dedup();
+                Some(breaks)
+            }
+            None => get_builtin_line_break_utf16(script, input),
+        },
+        None => None,
+    };
+    if let Some(breaks) = breaks {
+        result.extend(breaks.iter().map(|b| b + start));
+    }
+}
+
+// Return the breaks inside a run of complex language text. The run is broken where the script
+// changes, and each part of it by the segmenter of its script.
+pub(crate) fn get_line_break_utf16(
+    input: &[u16],
+    segmenters: &ComplexSegmenters,
+) -> Option<Vec<usize>> {
+    let mut result = Vec::new();
+    let mut start = 0;
+    let mut offset = 0;
+    let mut script = None;
+    for c in decode_utf16(input.iter().cloned()) {
+        let c = c.unwrap_or(REPLACEMENT_CHARACTER);
+        let c_script = get_complex_script(c as u32);
+        if offset > 0 && c_script != script {
+            push_script_breaks(&mut result, segmenters, script, input, start, offset);
+            result.push(offset);
+            start = offset;
+        }
+        script = c_script;
+        // A lone surrogate is one code unit as well as U+FFFD.
+        offset += c.len_utf16();
+    }
+    push_script_breaks(&mut result, segmenters, script, input, start, input.len());
+    if result.is_empty() {
+        return None;
+    }
+    Some(result)
+}
+
+// Split `s` into the segments between the breaks of `segment`, for the tests of segmenters.
+#[cfg(test)]
+pub(crate) fn get_segments<F>(s: &str, segment: F) -> Vec<String>
+where
+    F: Fn(&[u16]) -> Option<Vec<usize>>,
+{
+    let text: Vec<u16> = s.encode_utf16().collect();
+    let mut breaks = segment(&text).unwrap_or_default();
+    breaks.insert(0, 0);
+    breaks.push(text.len());
+    breaks
+        .windows(2)
+        .map(|w| String::from_utf16(&text[w[0]..w[1]]).unwrap())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::complex::{get_line_break_utf16, ComplexSegmenters};
+
+    #[test]
+    fn complex_script_runs() {
+        // The run is broken between Lao and Thai.
+        let text: Vec<u16> = "ລາວก".encode_utf16().collect();
+        assert_eq!(
+            get_line_break_utf16(&text, &ComplexSegmenters::default()).unwrap(),
+            [3]
+        );
+
+        let text: Vec<u16> = "ກ".encode_utf16().collect();
+        assert_eq!(
+            get_line_break_utf16(&text, &ComplexSegmenters::default()),
+            None
+        );
+    }
+}
@@ -0,0 +1,283 @@
+use crate::complex::is_cluster_boundary;
+use crate::ComplexSegmenter;
+
+use core::fmt;
+
+// A node of the trie. Its children are `edges[first_edge..first_edge + edge_count]`, which are
+// sorted by the code unit.
+#[derive(Clone)]
+struct TrieNode {
+    first_edge: u32,
+    edge_count: u32,
+    is_word: bool,
+}
+
+/// A word list for the dictionary-based segmenter of complex language text, like the
+/// dictionaries of ICU. The words are kept in a compact trie of UTF-16 code units, and the text
+/// is broken into the fewest words that cover the most of it (maximal matching).
+///
+/// This is smaller and faster than the LSTM model, but text that isn't in the dictionary is
+/// kept together until the next known word. Only the Lao and Khmer word lists of ICU come with
+/// this crate, in the `dictionaries` feature, so the caller provides the words of the other
+/// scripts.
+/// The LSTM model can be left out by disabling the `lstm` feature.
+///
+/// ```rust
+/// use std::sync::Arc;
+/// use uax14_rs::{ComplexScript, Dictionary, LineBreakIterator, LineBreakOptions};
+///
+/// let dictionary = Dictionary::new(["ภาษา", "ไทย", "ภา"].iter().cloned());
+/// let options = LineBreakOptions::new().dictionary(ComplexScript::Thai, Arc::new(dictionary));
+/// let breaks: Vec<usize> = LineBreakIterator::new_with_options("ภาษาไทย", &options).collect();
+/// assert_eq!(breaks, [12, 21]);
+/// ```
+#[derive(Clone)]
+pub struct Dictionary {
+    nodes: Vec<TrieNode>,
+    // The code unit and the child node of each edge.
+    edges: Vec<(u16, u32)>,
+    len: usize,
+}
+
+// MAIYAMOK (U+0E46) and KO LA (U+0EC6) repeat the previous word, so they belong to it.
+fn is_repetition_mark(c: u16) -> bool {
+    c == 0xe46 || c == 0xec6
+}
+
+impl Dictionary {
+    /// Create a dictionary of `words`
+    pub fn new<'a, I: IntoIterator<Item = &'a str>>(words: I) -> Dictionary {
+        let mut words: Vec<Vec<u16>> = words
+            .into_iter()
+            .filter(|w| !w.is_empty())
+            .map(|w| w.encode_utf16().collect())
+            .collect();
+        words.sort();
+        words.dedup();
+        let mut dictionary = Dictionary {
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            len: words.len(),
+        };
+        dictionary.add_node(&words, 0);
+        dictionary
+    }
+
+    // Add the node of `words` that share the first `depth` code units, and return its index.
+    fn add_node(&mut self, words: &[Vec<u16>], depth: usize) -> u32 {
+        let index = self.nodes.len();
+        let is_word = !words.is_empty() && words[0].len() == depth;
+        let words = if is_word { &words[1..] } else { words };
+        let mut groups = Vec::new();
+        let mut start = 0;
+        for i in 1..=words.len() {
+            if i == words.len() || words[i][depth] != words[start][depth] {
+                groups.push(start..i);
+                start = i;
+            }
+        }
+        let first_edge = self.edges.len();
+        self.nodes.push(TrieNode {
+            first_edge: first_edge as u32,
+            edge_count: groups.len() as u32,
+            is_word,
+        });
+        self.edges
+            .extend(groups.iter().map(|g| (words[g.start][depth], 0)));
+        for (i, group) in groups.into_iter().enumerate() {
+            let child = self.add_node(&words[group], depth + 1);
+            self.edges[first_edge + i].1 = child;
+        }
+        index as u32
+    }
+
+    /// Return the number of words in the dictionary
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Return whether the dictionary has no words
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Return whether `word` is in the dictionary
+    pub fn contains(&self, word: &str) -> bool {
+        let word: Vec<u16> = word.encode_utf16().collect();
+        let mut node = 0;
+        for c in word {
+            match self.get_child(node, c) {
+                Some(child) => node = child,
+                None => return false,
+            }
+        }
+        self.nodes[node].is_word
+    }
+
+    fn get_child(&self, node: usize, c: u16) -> Option<usize> {
+        let node = &self.nodes[node];
+        let start = node.first_edge as usize;
+        let edges = &self.edges[start..start + node.edge_count as usize];
+        edges
+            .binary_search_by_key(&c, |e| e.0)
+            .ok()
+            .map(|i| edges[i].1 as usize)
+    }
+
+    // Return the ends of the words that start at `start` of `input`.
+    fn find_words(&self, input: &[u16], start: usize) -> Vec<usize> {
+        let mut ends = Vec::new();
+        let mut node = 0;
+        for (i, &c) in input.iter().enumerate().skip(start) {
+            match self.get_child(node, c) {
+                Some(child) => node = child,
+                None => break,
+            }
+            if self.nodes[node].is_word {
+                ends.push(i + 1);
+            }
+        }
+        ends
+    }
+
+    pub(crate) fn get_line_break_utf16(&self, input: &[u16]) -> Option<Vec<usize>> {
+        self.get_line_break_utf16_with_fallback(input, |_| None)
+    }
+
+    // The same as `get_line_break_utf16`, but the characters out of words are broken by
+    // `fallback`, such as the syllable rules of the script.
+    pub(crate) fn get_line_break_utf16_with_fallback<F>(
+        &self,
+        input: &[u16],
+        fallback: F,
+    ) -> Option<Vec<usize>>
+    where
+        F: Fn(&[u16]) -> Option<Vec<usize>>,
+    {
+        // For each position, the fewest characters out of words and the fewest words to reach
+        // it, the previous position, and whether it is reached by a word.
+        let mut best: Vec<Option<(usize, usize, usize, bool)>> = vec![None; input.len() + 1];
+        best[0] = Some((0, 0, 0, false));
+        for start in 0..input.len() {
+            let (unknown, words, _, _) = match best[start] {
+                Some(data) => data,
+                None => continue,
+            };
+            let mut candidates = vec![(start + 1, unknown + 1, words, false)];
+            if is_cluster_boundary(input, start) {
+                for mut end in self.find_words(input, start) {
+                    while matches!(input.get(end), Some(&c) if is_repetition_mark(c)) {
+                        end += 1;
+                    }
+                    if is_cluster_boundary(input, end) {
+                        candidates.push((end, unknown, words + 1, true));
+                    }
+                }
+            }
+            for (end, unknown, words, is_word) in candidates {
+                let better = match best[end] {
+                    Some((u, w, _, _)) => (unknown, words) < (u, w),
+                    None => true,
+                };
+                if better {
+                    best[end] = Some((unknown, words, start, is_word));
+                }
+            }
+        }
+
+        // The words, and the runs of characters out of words between them.
+        let mut segments: Vec<(usize, usize, bool)> = Vec::new();
+        let mut end = input.len();
+        while end > 0 {
+            let (_, _, start, is_word) = best[end].unwrap();
+            match segments.last_mut() {
+                Some(last) if !is_word && !last.2 => last.0 = start,
+                _ => segments.push((start, end, is_word)),
+            }
+            end = start;
+        }
+
+        // Break around words, and the characters out of words only by `fallback`.
+        let mut result = Vec::new();
+        for &(start, end, is_word) in segments.iter().rev() {
+            if start > 0 {
+                result.push(start);
+            }
+            if !is_word {
+                if let Some(breaks) = fallback(&input[start..end]) {
+                    result.extend(breaks.iter().map(|b| b + start));
+                }
+            }
+        }
+        if result.is_empty() {
+            return None;
+        }
+        Some(result)
+    }
+}
+
+impl ComplexSegmenter for Dictionary {
+    fn segment_utf16(&self, input: &[u16]) -> Vec<usize> {
+        self.get_line_break_utf16(input).unwrap_or_default()
+    }
+}
+
+impl fmt::Debug for Dictionary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Dictionary")
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::complex::get_segments;
+    use crate::dictionary::Dictionary;
+
+    fn get_words(dictionary: &Dictionary, s: &str) -> Vec<String> {
+        get_segments(s, |text| dictionary.get_line_break_utf16(text))
+    }
+
+    #[test]
+    fn dictionary_trie() {
+        let dictionary = Dictionary::new(["ไทย", "ภาษา", "ภา", "ษา", "ภาษา", ""].iter().cloned());
+        assert_eq!(dictionary.len(), 4);
+        assert!(dictionary.contains("ภา"));
+        assert!(dictionary.contains("ภาษา"));
+        assert!(!dictionary.contains("ภาษ"));
+        assert!(!dictionary.contains(""));
+        assert!(Dictionary::new(None).is_empty());
+    }
+
+    #[test]
+    fn dictionary_maximal_matching() {
+        let dictionary = Dictionary::new(
+            ["ไป", "ไปมา", "มา", "มาก", "กิน", "ข้าว", "ตา"]
+                .iter()
+                .cloned(),
+        );
+        // The fewest words.
+        assert_eq!(get_words(&dictionary, "ไปมา"), ["ไปมา"]);
+        // The most characters in words before the fewest words.
+        assert_eq!(get_words(&dictionary, "มากิน"), ["มา", "กิน"]);
+        // Unknown characters are kept together, and a word doesn't end before a vowel sign.
+        assert_eq!(get_words(&dictionary, "กินขนมข้าว"), ["กิน", "ขนม", "ข้าว"]);
+        assert_eq!(get_words(&dictionary, "ตาี"), ["ตาี"]);
+        assert_eq!(get_words(&dictionary, "ข้าวๆ"), ["ข้าวๆ"]);
+        // MAIYAMOK belongs to the word before it.
+        assert_eq!(get_words(&dictionary, "ข้าวๆตา"), ["ข้าวๆ", "ตา"]);
+    }
+
+    #[test]
+    fn dictionary_fallback() {
+        let dictionary = Dictionary::new(["ไทย"].iter().cloned());
+        // Only the characters out of words are broken by the fallback.
+        let words = get_segments("กขไทยคง", |text| {
+            dictionary.get_line_break_utf16_with_fallback(text, |unknown| {
+                Some((1..unknown.len()).collect())
+            })
+        });
+        assert_eq!(words, ["ก", "ข", "ไทย", "ค", "ง"]);
+    }
+}
@@ -0,0 +1,111 @@
+// Khmer text is broken between words by the Khmer word list of ICU in the `dictionaries`
+// feature, as ICU does. Text that isn't in the word list, and all Khmer text without the
+// feature, is broken between orthographic clusters that certainly start a syllable. A cluster is
+// a consonant or an independent vowel with the subscripts of COENG (U+17D2), vowels and signs
+// after it, and is never broken. That isn't word segmentation: a word such as ភាសា is broken as
+// ភា|សា.
+
+#[cfg(feature = "dictionaries")]
+use crate::dictionary::Dictionary;
+
+#[cfg(feature = "dictionaries")]
+lazy_static! {
+    static ref KHMER_DICTIONARY: Dictionary =
+        Dictionary::new(include_str!("../data/dictionary/khmer.txt").lines());
+}
+
+// Consonants and independent vowels: U+1780..U+17B3
+fn is_base(c: u16) -> bool {
+    (0x1780..=0x17b3).contains(&c)
+}
+
+const COENG: u16 = 0x17d2;
+
+// Dependent vowels: U+17B6..U+17C5
+fn is_dependent_vowel(c: u16) -> bool {
+    (0x17b6..=0x17c5).contains(&c)
+}
+
+// Vowels, signs and COENG that belong to the previous base: U+17B4..U+17D3 and U+17DD
+fn is_mark(c: u16) -> bool {
+    (0x17b4..=0x17d3).contains(&c) || c == 0x17dd
+}
+
+// REAHMUK (U+17C7) and YUUKALEAPINTU (U+17C8) end a syllable.
+fn is_syllable_end(c: u16) -> bool {
+    c == 0x17c7 || c == 0x17c8
+}
+
+fn is_cluster_start(input: &[u16], i: usize) -> bool {
+    is_base(input[i]) && (i == 0 || input[i - 1] != COENG)
+}
+
+fn has_dependent_vowel(input: &[u16], start: usize) -> bool {
+    for i in start + 1..input.len() {
+        if is_cluster_start(input, i) {
+            return false;
+        }
+        if is_dependent_vowel(input[i]) {
+            return true;
+        }
+    }
+    false
+}
+
+pub fn is_cluster_boundary(input: &[u16], offset: usize) -> bool {
+    !(input[offset - 1] == COENG || is_mark(input[offset]))
+}
+
+// A cluster with a dependent vowel starts a syllable. A cluster without it may be the final
+// consonant of the previous syllable, so it isn't broken unless the previous one ends with
+// REAHMUK or YUUKALEAPINTU.
+#[cfg(feature = "dictionaries")]
+pub fn get_line_break_utf16(input: &[u16]) -> Option<Vec<usize>> {
+    KHMER_DICTIONARY.get_line_break_utf16_with_fallback(input, get_cluster_break_utf16)
+}
+
+#[cfg(not(feature = "dictionaries"))]
+pub fn get_line_break_utf16(input: &[u16]) -> Option<Vec<usize>> {
+    get_cluster_break_utf16(input)
+}
+
+fn get_cluster_break_utf16(input: &[u16]) -> Option<Vec<usize>> {
+    let mut result = Vec::new();
+    let mut ends_syllable = false;
+    for i in 1..input.len() {
+        if is_cluster_start(input, i) {
+            if ends_syllable || has_dependent_vowel(input, i) {
+                result.push(i);
+            }
+            ends_syllable = false;
+        }
+        ends_syllable |= is_syllable_end(input[i]);
+    }
+    if result.is_empty() {
+        return None;
+    }
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::complex::get_segments;
+    use crate::khmer::get_cluster_break_utf16;
+
+    fn get_syllables(s: &str) -> Vec<String> {
+        get_segments(s, get_cluster_break_utf16)
+    }
+
+    #[test]
+    fn khmer_cluster_break() {
+        assert_eq!(get_syllables("ភាសាខ្មែរ"), ["ភា", "សា", "ខ្មែរ"]);
+        assert_eq!(get_syllables("កម្ពុជា"), ["ក", "ម្ពុ", "ជា"]);
+        // Subscripts of COENG stay in their cluster.
+        assert_eq!(get_syllables("ស្ត្រី"), ["ស្ត្រី"]);
+        assert_eq!(get_syllables("សួស្តី"), ["សួ", "ស្តី"]);
+        // REAHMUK ends a syllable.
+        assert_eq!(get_syllables("ទះដៃ"), ["ទះ", "ដៃ"]);
+        assert_eq!(get_syllables("ទះក"), ["ទះ", "ក"]);
+        assert_eq!(get_syllables("ក"), ["ក"]);
+    }
+}
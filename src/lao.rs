@@ -0,0 +1,117 @@
+// Lao text is broken between words by the Lao word list of ICU in the `dictionaries` feature,
+// as ICU does. Text that isn't in the word list, and all Lao text without the feature, is broken
+// between syllables that are certain from the spelling. That doesn't find word boundaries: a
+// word of several syllables such as ພາສາ is broken as ພາ|ສາ, but a line is still wrapped
+// without overflowing.
+
+#[cfg(feature = "dictionaries")]
+use crate::dictionary::Dictionary;
+
+#[cfg(feature = "dictionaries")]
+lazy_static! {
+    static ref LAO_DICTIONARY: Dictionary =
+        Dictionary::new(include_str!("../data/dictionary/lao.txt").lines());
+}
+
+// U+0E81..U+0EAE and U+0EDC..U+0EDF
+fn is_consonant(c: u16) -> bool {
+    (0xe81..=0xeae).contains(&c) || (0xedc..=0xedf).contains(&c)
+}
+
+// Vowels written before the consonant: U+0EC0..U+0EC4
+fn is_preposed_vowel(c: u16) -> bool {
+    (0xec0..=0xec4).contains(&c)
+}
+
+// Vowels, tone marks and signs written after, above or below the consonant.
+fn is_dependent(c: u16) -> bool {
+    (0xeb0..=0xebd).contains(&c) || (0xec8..=0xece).contains(&c)
+}
+
+// KO LA (U+0EC6) repeats the previous word.
+const KO_LA: u16 = 0xec6;
+
+// Consonants that follow HO SUNG (U+0EAB) in the same syllable.
+fn is_sonorant(c: u16) -> bool {
+    matches!(c, 0xe87 | 0xe8d | 0xe99 | 0xea1 | 0xea3 | 0xea5 | 0xea7)
+}
+
+// A syllable starts at a preposed vowel, and at a consonant with its own vowel or tone mark
+// unless it follows another consonant in the same syllable. A short vowel SARA A (U+0EB0) or
+// SARA AM (U+0EB3) ends a syllable.
+fn is_syllable_start(input: &[u16], i: usize) -> bool {
+    let c = input[i];
+    let prev = input[i - 1];
+    if is_preposed_vowel(c) {
+        return true;
+    }
+    if !is_consonant(c) || is_preposed_vowel(prev) {
+        return false;
+    }
+    if prev == 0xeb0 || prev == 0xeb3 {
+        return true;
+    }
+    match input.get(i + 1) {
+        Some(&next) if is_dependent(next) => {
+            !(is_consonant(prev) && (c == 0xea7 || (prev == 0xeab && is_sonorant(c))))
+        }
+        _ => false,
+    }
+}
+
+pub fn is_cluster_boundary(input: &[u16], offset: usize) -> bool {
+    let c = input[offset];
+    !(is_preposed_vowel(input[offset - 1]) || is_dependent(c) || c == KO_LA)
+}
+
+#[cfg(feature = "dictionaries")]
+pub fn get_line_break_utf16(input: &[u16]) -> Option<Vec<usize>> {
+    LAO_DICTIONARY.get_line_break_utf16_with_fallback(input, get_syllable_break_utf16)
+}
+
+#[cfg(not(feature = "dictionaries"))]
+pub fn get_line_break_utf16(input: &[u16]) -> Option<Vec<usize>> {
+    get_syllable_break_utf16(input)
+}
+
+fn get_syllable_break_utf16(input: &[u16]) -> Option<Vec<usize>> {
+    let mut result = Vec::new();
+    let mut start = 0;
+    for i in 1..input.len() {
+        // A consonant isn't a syllable by itself.
+        if i - start >= 2 && is_syllable_start(input, i) {
+            result.push(i);
+            start = i;
+        }
+    }
+    if result.is_empty() {
+        return None;
+    }
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::complex::get_segments;
+    use crate::lao::get_syllable_break_utf16;
+
+    fn get_syllables(s: &str) -> Vec<String> {
+        get_segments(s, get_syllable_break_utf16)
+    }
+
+    #[test]
+    fn lao_syllable_break() {
+        assert_eq!(get_syllables("ສະບາຍດີ"), ["ສະ", "ບາຍ", "ດີ"]);
+        assert_eq!(get_syllables("ພາສາລາວ"), ["ພາ", "ສາ", "ລາວ"]);
+        assert_eq!(get_syllables("ຂອບໃຈຫຼາຍໆ"), ["ຂອບ", "ໃຈ", "ຫຼາຍໆ"]);
+        assert_eq!(
+            get_syllables("ເຈົ້າເປັນຄົນລາວບໍ່"),
+            ["ເຈົ້າ", "ເປັນ", "ຄົນ", "ລາວ", "ບໍ່"]
+        );
+        // Clusters with HO SUNG and WO aren't broken.
+        assert_eq!(get_syllables("ຄວາມສຸກ"), ["ຄວາມ", "ສຸກ"]);
+        assert_eq!(get_syllables("ຫລາຍ"), ["ຫລາຍ"]);
+        assert_eq!(get_syllables("ເຫຼົ້າ"), ["ເຫຼົ້າ"]);
+        assert_eq!(get_syllables("ກ"), ["ກ"]);
+    }
+}
@@ -61,3 +61,63 @@ pub const PROP_COUNT: usize = 57;
 pub const BREAK_RULE: i8 = -128;
 pub const PREVIOUS_BREAK_RULE: i8 = -2;
 pub const KEEP_RULE: i8 = -1;
+
+pub const PROPERTY_NAMES: [&str; PROP_COUNT] = [
+    "AI",
+    "AL",
+    "B2",
+    "BA",
+    "BB",
+    "BK",
+    "CB",
+    "CJ",
+    "CL",
+    "CM",
+    "CP",
+    "CR",
+    "EB",
+    "EM",
+    "EX",
+    "GL",
+    "H2",
+    "H3",
+    "HL",
+    "HY",
+    "ID",
+    "IN",
+    "IS",
+    "JL",
+    "JT",
+    "JV",
+    "LF",
+    "NL",
+    "NS",
+    "NU",
+    "OP_EA",
+    "OP_OP30",
+    "PO",
+    "PR",
+    "QU",
+    "RI",
+    "SA",
+    "SG",
+    "SP",
+    "SY",
+    "WJ",
+    "XX",
+    "ZW",
+    "ZWJ",
+    "B2_SP",
+    "CL_CP_SP",
+    "HL_HY",
+    "LB25_HY",
+    "LB25_OP",
+    "LB25_NU_IS",
+    "LB25_NU_SY",
+    "LB25_NU_CL",
+    "LB25_NU_CP",
+    "OP_SP",
+    "QU_SP",
+    "RI_RI",
+    "EOT",
+];
@@ -10,24 +10,65 @@
 //! }
 //! ```
 
+mod complex;
+mod dictionary;
+mod khmer;
+mod lao;
 mod lb_define;
+mod line_break_class;
+#[cfg(feature = "encoding_rs")]
+mod line_break_encoding;
+mod line_break_reader;
+mod line_break_stream;
 mod line_breaker;
+#[cfg(feature = "lstm")]
 mod lstm;
+mod myanmar;
 mod properties_defines;
 mod properties_other;
 mod property_table;
 mod rule_table;
+mod thai;
+pub mod wrap;
 
+#[cfg(any(feature = "lstm", feature = "dictionaries"))]
 #[macro_use]
 extern crate lazy_static;
 
+pub use crate::complex::{ComplexScript, ComplexSegmenter};
+pub use crate::dictionary::Dictionary;
+pub use crate::line_break_class::*;
+#[cfg(feature = "encoding_rs")]
+pub use crate::line_break_encoding::*;
+pub use crate::line_break_reader::*;
+pub use crate::line_break_stream::*;
 pub use crate::line_breaker::*;
 
 #[cfg(test)]
 mod tests {
+    use crate::line_break_segments;
+    use crate::BreakDecision;
+    use crate::BreakKind;
+    use crate::BreakRules;
+    use crate::ComplexScript;
+    use crate::ComplexSegmenter;
+    use crate::Dictionary;
     use crate::LineBreakIterator;
     use crate::LineBreakIteratorLatin1;
+    use crate::LineBreakIteratorPieces;
+    use crate::LineBreakIteratorPotentiallyIllFormedUTF8;
     use crate::LineBreakIteratorUTF16;
+    use crate::LineBreakIteratorUTF32;
+    use crate::LineBreakOptions;
+    use crate::LineBreakReader;
+    use crate::LineBreakRule;
+    use crate::LineBreakStream;
+    use crate::LineBreakStreamUTF16;
+    use crate::LineSegment;
+    use crate::LineSegmentReader;
+    use crate::WordBreakRule;
+    use crate::DEFAULT_MAX_LOOKAHEAD;
+    use std::sync::Arc;
 
     #[test]
     fn linebreak() {
@@ -109,4 +150,833 @@ mod tests {
         iter = LineBreakIterator::new("\u{1F3FB} \u{1F3FB}");
         assert_eq!(Some(5), iter.next());
     }
+
+    #[test]
+    fn linebreak_kind() {
+        let iter = LineBreakIterator::new("abc def\r\nghi\njkl\r");
+        let result: Vec<(usize, BreakKind)> = iter.with_break_kind().collect();
+        assert_eq!(
+            result,
+            [
+                (4, BreakKind::Allowed),
+                (9, BreakKind::Mandatory),
+                (13, BreakKind::Mandatory),
+                (17, BreakKind::Mandatory),
+            ]
+        );
+
+        let iter = LineBreakIterator::new("abc def");
+        let result: Vec<(usize, BreakKind)> = iter.with_break_kind().collect();
+        assert_eq!(result, [(4, BreakKind::Allowed), (7, BreakKind::Allowed)]);
+
+        // NEL and LS
+        let iter = LineBreakIterator::new("a\u{0085}b\u{2028}c");
+        let result: Vec<(usize, BreakKind)> = iter.with_break_kind().collect();
+        assert_eq!(
+            result,
+            [
+                (3, BreakKind::Mandatory),
+                (7, BreakKind::Mandatory),
+                (8, BreakKind::Allowed),
+            ]
+        );
+
+        let input: [u8; 8] = [0x61, 0x20, 0x62, 0x0D, 0x0A, 0x63, 0x0C, 0x64];
+        let iter = LineBreakIteratorLatin1::new(&input);
+        let result: Vec<(usize, BreakKind)> = iter.with_break_kind().collect();
+        assert_eq!(
+            result,
+            [
+                (2, BreakKind::Allowed),
+                (5, BreakKind::Mandatory),
+                (7, BreakKind::Mandatory),
+                (8, BreakKind::Allowed),
+            ]
+        );
+
+        let input: [u16; 7] = [0x61, 0x20, 0xD83D, 0xDE00, 0x0A, 0x63, 0x0D];
+        let iter = LineBreakIteratorUTF16::new(&input);
+        let result: Vec<(usize, BreakKind)> = iter.with_break_kind().collect();
+        assert_eq!(
+            result,
+            [
+                (2, BreakKind::Allowed),
+                (5, BreakKind::Mandatory),
+                (7, BreakKind::Mandatory),
+            ]
+        );
+    }
+
+    #[test]
+    fn linebreak_trace() {
+        let trace: Vec<(usize, BreakDecision, &str, &str, &str)> =
+            LineBreakIterator::new("a\u{0301}b\r\n$(c d")
+                .trace_rules()
+                .iter()
+                .map(|t| (t.offset, t.decision, t.left_class, t.right_class, t.rule))
+                .collect();
+        assert_eq!(
+            trace,
+            [
+                (1, BreakDecision::Prohibited, "AL", "CM", "LB9"),
+                (3, BreakDecision::Prohibited, "AL", "AL", "LB28"),
+                (4, BreakDecision::Prohibited, "AL", "CR", "LB6"),
+                (5, BreakDecision::Prohibited, "CR", "LF", "LB5"),
+                (6, BreakDecision::Mandatory, "LF", "PR", "LB5"),
+                (7, BreakDecision::Allowed, "PR", "OP_OP30", "LB31"),
+                (8, BreakDecision::Prohibited, "OP_OP30", "AL", "LB14"),
+                (9, BreakDecision::Prohibited, "AL", "SP", "LB7"),
+                (10, BreakDecision::Allowed, "SP", "AL", "LB18"),
+            ]
+        );
+
+        let input: [u16; 6] = [0x28, 0x30, 0x2C, 0x31, 0x29, 0x25];
+        let rules: Vec<&str> = LineBreakIteratorUTF16::new(&input)
+            .trace_rules()
+            .iter()
+            .map(|t| t.rule)
+            .collect();
+        assert_eq!(rules, ["LB14", "LB25", "LB25", "LB25", "LB25"]);
+
+        assert_eq!(format!("{}", BreakDecision::Allowed), "\u{00f7}");
+    }
+
+    #[test]
+    fn linebreak_random_access() {
+        // Breaks are 6, 16, 21 and 23.
+        let iter = LineBreakIterator::new("hello (  world) 12,3\nab");
+        assert_eq!(iter.following(0), Some(6));
+        assert_eq!(iter.following(6), Some(16));
+        assert_eq!(iter.following(10), Some(16));
+        assert_eq!(iter.following(21), Some(23));
+        assert_eq!(iter.following(23), None);
+        assert_eq!(iter.preceding(23), Some(21));
+        assert_eq!(iter.preceding(16), Some(6));
+        assert_eq!(iter.preceding(6), None);
+        assert!(iter.is_boundary(6));
+        assert!(!iter.is_boundary(9));
+        assert!(iter.is_boundary(16));
+        assert!(!iter.is_boundary(19));
+        assert!(iter.is_boundary(23));
+        assert!(!iter.is_boundary(0));
+
+        let input: [u16; 7] = [0x61, 0x20, 0xD83D, 0xDE00, 0x20, 0x62, 0x63];
+        let iter = LineBreakIteratorUTF16::new(&input);
+        assert_eq!(iter.following(0), Some(2));
+        assert_eq!(iter.following(2), Some(5));
+        assert_eq!(iter.preceding(5), Some(2));
+        assert!(iter.is_boundary(5));
+        assert!(!iter.is_boundary(6));
+
+        let input = b"a  (b c";
+        let iter = LineBreakIteratorLatin1::new(input);
+        assert_eq!(iter.following(1), Some(3));
+        assert_eq!(iter.preceding(6), Some(3));
+    }
+
+    #[test]
+    fn linebreak_reverse() {
+        let input = "(0,1)+(2,3) $-1.5 \u{1F1EF}\u{1F1F5}\u{1F1EF}\u{1F1F5}\u{1F1EF} \u{0E20}\u{0E32}\u{0E29}\u{0E32}\u{0E44}\u{0E17}\u{0E22}";
+        let forward: Vec<usize> = LineBreakIterator::new(input).collect();
+        let mut backward: Vec<usize> = LineBreakIterator::new(input).rev().collect();
+        backward.reverse();
+        assert_eq!(forward, backward);
+
+        let mut iter = LineBreakIterator::new(input);
+        assert_eq!(iter.next(), forward.first().cloned());
+        assert_eq!(iter.next_back(), forward.last().cloned());
+        let rest: Vec<usize> = iter.collect();
+        assert_eq!(rest, forward[1..forward.len() - 1]);
+
+        let utf16: Vec<u16> = input.encode_utf16().collect();
+        let forward: Vec<usize> = LineBreakIteratorUTF16::new(&utf16).collect();
+        let mut backward: Vec<usize> = LineBreakIteratorUTF16::new(&utf16).rev().collect();
+        backward.reverse();
+        assert_eq!(forward, backward);
+
+        let input = b"(0,1)+(2,3) $-1.5 abc\r\n\r\nd";
+        let forward: Vec<usize> = LineBreakIteratorLatin1::new(input).collect();
+        let mut backward: Vec<usize> = LineBreakIteratorLatin1::new(input).rev().collect();
+        backward.reverse();
+        assert_eq!(forward, backward);
+        assert_eq!(forward, [12, 18, 23, 25, 26]);
+    }
+
+    #[test]
+    fn linebreak_update() {
+        let old = "The quick brown fox jumps over the lazy dog.";
+        let old_breaks: Vec<usize> = LineBreakIterator::new(old).collect();
+
+        // Replace "brown" with "red-brown".
+        let new = "The quick red-brown fox jumps over the lazy dog.";
+        let iter = LineBreakIterator::new(new);
+        let breaks = iter.update_breaks(&old_breaks, 10..15, 9);
+        assert_eq!(breaks, LineBreakIterator::new(new).collect::<Vec<usize>>());
+        assert_eq!(breaks, [4, 10, 14, 20, 24, 30, 35, 39, 44, 48]);
+
+        // Remove " fox".
+        let new = "The quick brown jumps over the lazy dog.";
+        let iter = LineBreakIterator::new(new);
+        let breaks = iter.update_breaks(&old_breaks, 15..19, 0);
+        assert_eq!(breaks, LineBreakIterator::new(new).collect::<Vec<usize>>());
+
+        let old: Vec<u16> = "(1) 2 3".encode_utf16().collect();
+        let old_breaks: Vec<usize> = LineBreakIteratorUTF16::new(&old).collect();
+        let new: Vec<u16> = "$(1) 2 3".encode_utf16().collect();
+        let iter = LineBreakIteratorUTF16::new(&new);
+        let breaks = iter.update_breaks(&old_breaks, 0..0, 1);
+        assert_eq!(breaks, [5, 7, 8]);
+    }
+
+    #[test]
+    fn linebreak_stream() {
+        let s = "$(12) 3,4 abc\r\nกากกา ภาษาไทยภาษาไทย 🇯🇵🇺🇸 ";
+        let expected: Vec<(usize, BreakKind)> =
+            LineBreakIterator::new(s).with_break_kind().collect();
+        for size in 1..8 {
+            let mut stream = LineBreakStream::new();
+            let mut breaks = Vec::new();
+            let mut start = 0;
+            while start < s.len() {
+                let mut end = core::cmp::min(start + size, s.len());
+                while !s.is_char_boundary(end) {
+                    end += 1;
+                }
+                breaks.extend(stream.feed(&s[start..end]));
+                start = end;
+            }
+            breaks.extend(stream.finish());
+            assert_eq!(breaks, expected, "chunk size {}", size);
+        }
+
+        let s: Vec<u16> = s.encode_utf16().collect();
+        let expected: Vec<(usize, BreakKind)> =
+            LineBreakIteratorUTF16::new(&s).with_break_kind().collect();
+        for size in 1..8 {
+            let mut stream = LineBreakStreamUTF16::new();
+            let mut breaks = Vec::new();
+            for chunk in s.chunks(size) {
+                breaks.extend(stream.feed(chunk));
+            }
+            breaks.extend(stream.finish());
+            assert_eq!(breaks, expected, "chunk size {}", size);
+        }
+
+        // Nothing is confirmed until the numeric sequence ends.
+        let mut stream = LineBreakStream::new();
+        assert_eq!(stream.feed("a (1"), [(2, BreakKind::Allowed)]);
+        assert!(stream.feed("2,3").is_empty());
+        assert_eq!(stream.feed(") b"), [(9, BreakKind::Allowed)]);
+        assert_eq!(stream.finish(), [(10, BreakKind::Allowed)]);
+    }
+
+    #[test]
+    fn linebreak_stream_bounded() {
+        // Digits have no point where the breaks are settled, so they are kept until the limit.
+        let s = "1".repeat(10_000);
+        let mut stream = LineBreakStream::new().max_lookahead(100);
+        let mut breaks = Vec::new();
+        for chunk in s.as_bytes().chunks(7) {
+            breaks.extend(stream.feed(core::str::from_utf8(chunk).unwrap()));
+        }
+        breaks.extend(stream.finish());
+        let (last, forced) = breaks.split_last().unwrap();
+        assert_eq!(*last, (s.len(), BreakKind::Allowed));
+        assert!(forced.len() > 1);
+        let mut start = 0;
+        for &(b, kind) in forced {
+            assert_eq!(kind, BreakKind::Forced);
+            assert!(b > start && b - start <= 100 + 7);
+            start = b;
+        }
+
+        let utf16: Vec<u16> = s.encode_utf16().collect();
+        let mut stream = LineBreakStreamUTF16::new().max_lookahead(100);
+        let mut breaks_utf16 = Vec::new();
+        for chunk in utf16.chunks(7) {
+            breaks_utf16.extend(stream.feed(chunk));
+        }
+        breaks_utf16.extend(stream.finish());
+        assert_eq!(breaks_utf16, breaks);
+
+        // Without the limit, and with text shorter than the limit, the breaks are the same as the
+        // ones of the iterator.
+        let s = format!("{} {} ภาษาไทย🇯🇵", "1".repeat(300), "ສະບາຍດີ".repeat(20));
+        let expected: Vec<(usize, BreakKind)> =
+            LineBreakIterator::new(&s).with_break_kind().collect();
+        for max_lookahead in [None, Some(1000)].iter() {
+            let mut stream = LineBreakStream::new();
+            if let Some(max_lookahead) = max_lookahead {
+                stream = stream.max_lookahead(*max_lookahead);
+            }
+            let mut breaks = Vec::new();
+            for c in s.chars() {
+                breaks.extend(stream.feed(c.encode_utf8(&mut [0; 4])));
+            }
+            breaks.extend(stream.finish());
+            assert_eq!(breaks, expected);
+        }
+        let utf16: Vec<u16> = s.encode_utf16().collect();
+        let expected: Vec<(usize, BreakKind)> = LineBreakIteratorUTF16::new(&utf16)
+            .with_break_kind()
+            .collect();
+        let mut stream = LineBreakStreamUTF16::new().max_lookahead(1000);
+        let mut breaks = Vec::new();
+        for chunk in utf16.chunks(1) {
+            breaks.extend(stream.feed(chunk));
+        }
+        breaks.extend(stream.finish());
+        assert_eq!(breaks, expected);
+    }
+
+    #[test]
+    fn linebreak_pieces() {
+        let s = "$(12) 3,4 abc\r\nกากกา ภาษาไทยภาษาไทย 🇯🇵🇺🇸 ";
+        let expected: Vec<usize> = LineBreakIterator::new(s).collect();
+        let chars: Vec<usize> = s.char_indices().map(|(i, _)| i).collect();
+        for size in 1..8 {
+            let mut pieces = Vec::new();
+            let mut start = 0;
+            for end in chars.iter().skip(size).step_by(size) {
+                pieces.push(&s[start..*end]);
+                pieces.push("");
+                start = *end;
+            }
+            pieces.push(&s[start..]);
+            assert_eq!(
+                LineBreakIteratorPieces::new(&pieces).collect::<Vec<usize>>(),
+                expected
+            );
+            assert_eq!(
+                LineBreakIteratorPieces::new(&pieces)
+                    .rev()
+                    .collect::<Vec<usize>>(),
+                expected.iter().rev().cloned().collect::<Vec<usize>>()
+            );
+            for &offset in chars.iter() {
+                assert_eq!(
+                    LineBreakIteratorPieces::new(&pieces).following(offset),
+                    LineBreakIterator::new(s).following(offset)
+                );
+            }
+        }
+
+        assert_eq!(LineBreakIteratorPieces::new(&[]).next(), None);
+        assert_eq!(LineBreakIteratorPieces::new(&["", ""]).next(), None);
+    }
+
+    #[test]
+    fn linebreak_rule_runs() {
+        let s = "한국어텍스트 foobar 한국어";
+        let keep_all = BreakRules {
+            word_break_rule: WordBreakRule::KeepAll,
+            ..Default::default()
+        };
+        let break_all = BreakRules {
+            word_break_rule: WordBreakRule::BreakAll,
+            ..Default::default()
+        };
+        let iter = LineBreakIterator::new(s);
+        let breaks = iter.breaks_with_rule_runs(&[(0..18, keep_all), (19..25, break_all)]);
+        assert_eq!(breaks, [19, 20, 21, 22, 23, 24, 26, 29, 32, 35]);
+        let breaks = iter.breaks_with_rule_runs(&[(0..s.len(), keep_all)]);
+        assert_eq!(
+            breaks,
+            LineBreakIterator::new_with_break_rule(
+                s,
+                LineBreakRule::Strict,
+                WordBreakRule::KeepAll,
+                false
+            )
+            .collect::<Vec<usize>>()
+        );
+        assert_eq!(
+            iter.breaks_with_rule_runs(&[]),
+            LineBreakIterator::new(s).collect::<Vec<usize>>()
+        );
+
+        let s: Vec<u16> = s.encode_utf16().collect();
+        let iter = LineBreakIteratorUTF16::new(&s);
+        let breaks = iter.breaks_with_rule_runs(&[(0..6, keep_all), (7..13, break_all)]);
+        assert_eq!(breaks, [7, 8, 9, 10, 11, 12, 14, 15, 16, 17]);
+    }
+
+    #[test]
+    fn linebreak_options() {
+        // U+301C is breakable with `line-break: normal;` only for Chinese and Japanese.
+        let s = "\u{3042}\u{301C}\u{3042}";
+        let options = LineBreakOptions::new().line_break_rule(LineBreakRule::Normal);
+        let breaks: Vec<usize> = LineBreakIterator::new_with_options(s, &options).collect();
+        assert_eq!(breaks, [6, 9]);
+        for language in ["ja", "zh-Hant-TW", "ZH_cn", "ja-JP-u-lb-loose"].iter() {
+            let options = options.clone().language(language);
+            assert_eq!(options.get_language(), Some(*language));
+            let breaks: Vec<usize> = LineBreakIterator::new_with_options(s, &options).collect();
+            assert_eq!(breaks, [3, 6, 9], "{}", language);
+        }
+        for language in ["ko", "jav", "en-JP", ""].iter() {
+            let options = options.clone().language(language);
+            let breaks: Vec<usize> = LineBreakIterator::new_with_options(s, &options).collect();
+            assert_eq!(breaks, [6, 9], "{}", language);
+        }
+
+        let options = LineBreakOptions::new()
+            .word_break_rule(WordBreakRule::BreakAll)
+            .language("zh");
+        let s: Vec<u16> = "ab\u{301C}".encode_utf16().collect();
+        let breaks: Vec<usize> = LineBreakIteratorUTF16::new_with_options(&s, &options).collect();
+        let expected: Vec<usize> = LineBreakIteratorUTF16::new_with_break_rule(
+            &s,
+            LineBreakRule::Strict,
+            WordBreakRule::BreakAll,
+            true,
+        )
+        .collect();
+        assert_eq!(breaks, expected);
+    }
+
+    #[test]
+    fn linebreak_latin1_rules() {
+        let mut all_latin1: Vec<u8> = (0x01..=0xff).collect();
+        all_latin1.extend(b"\r\n");
+        let samples: [&[u8]; 4] = [
+            b"$(12.5) 10% -3 a-b c--d e/f \"g\" h!? i\tj",
+            b"\xa3100 50\xb0C \xabquote\xbb \xbfque? \xa110 \xb1 1\xbd \xa7 2",
+            b"a\xadb \xa0c d\xb4e \xa9 f\xb7g",
+            &all_latin1,
+        ];
+        let line_break_rules = [
+            LineBreakRule::Strict,
+            LineBreakRule::Normal,
+            LineBreakRule::Loose,
+            LineBreakRule::Anywhere,
+        ];
+        let word_break_rules = [
+            WordBreakRule::Normal,
+            WordBreakRule::BreakAll,
+            WordBreakRule::KeepAll,
+        ];
+        for latin1 in samples.iter() {
+            let s: String = latin1.iter().map(|&c| c as char).collect();
+            let utf16: Vec<u16> = s.encode_utf16().collect();
+            for &line_break_rule in line_break_rules.iter() {
+                for &word_break_rule in word_break_rules.iter() {
+                    for &ja_zh in [false, true].iter() {
+                        let breaks: Vec<usize> = LineBreakIteratorLatin1::new_with_break_rule(
+                            latin1,
+                            line_break_rule,
+                            word_break_rule,
+                            ja_zh,
+                        )
+                        .collect();
+                        let breaks_utf16: Vec<usize> = LineBreakIteratorUTF16::new_with_break_rule(
+                            &utf16,
+                            line_break_rule,
+                            word_break_rule,
+                            ja_zh,
+                        )
+                        .collect();
+                        // Convert UTF-8 offsets to character offsets.
+                        let breaks_utf8: Vec<usize> = LineBreakIterator::new_with_break_rule(
+                            &s,
+                            line_break_rule,
+                            word_break_rule,
+                            ja_zh,
+                        )
+                        .map(|b| s[..b].chars().count())
+                        .collect();
+                        assert_eq!(breaks, breaks_utf16, "{:?}", s);
+                        assert_eq!(breaks, breaks_utf8, "{:?}", s);
+                    }
+                }
+            }
+        }
+
+        // `line-break: loose;` breaks before the ambiguous width postfix for Chinese and Japanese.
+        for &(ja_zh, expected) in [(false, &[2][..]), (true, &[1, 2][..])].iter() {
+            let breaks: Vec<usize> = LineBreakIteratorLatin1::new_with_break_rule(
+                b"x\xb0",
+                LineBreakRule::Loose,
+                WordBreakRule::Normal,
+                ja_zh,
+            )
+            .collect();
+            assert_eq!(breaks, expected);
+        }
+        let breaks: Vec<usize> = LineBreakIteratorLatin1::new_with_break_rule(
+            b"abc",
+            LineBreakRule::Strict,
+            WordBreakRule::BreakAll,
+            false,
+        )
+        .collect();
+        assert_eq!(breaks, [1, 2, 3]);
+    }
+
+    #[test]
+    fn linebreak_utf32() {
+        let s = "Hello 世界。$(12.5) ภาษาไทยภาษาไทย 🇯🇵🇺🇸 😀!\r\nEnd";
+        let chars: Vec<char> = s.chars().collect();
+        let utf32: Vec<u32> = s.chars().map(|c| c as u32).collect();
+        let expected: Vec<usize> = LineBreakIterator::new(s)
+            .map(|b| s[..b].chars().count())
+            .collect();
+        let breaks: Vec<usize> = LineBreakIteratorUTF32::new(&utf32).collect();
+        assert_eq!(breaks, expected);
+        let options = LineBreakOptions::default();
+        let breaks: Vec<usize> = LineBreakIteratorUTF32::new_with_chars(&chars, &options).collect();
+        assert_eq!(breaks, expected);
+
+        // Thai is handled by the complex language breaker.
+        let thai: Vec<u32> = "ภาษาไทยภาษาไทย".chars().map(|c| c as u32).collect();
+        let breaks: Vec<usize> = LineBreakIteratorUTF32::new(&thai).collect();
+        #[cfg(feature = "lstm")]
+        assert_eq!(breaks, [4, 7, 11, 14]);
+        let expected: Vec<usize> = LineBreakIterator::new("ภาษาไทยภาษาไทย")
+            .map(|b| "ภาษาไทยภาษาไทย"[..b].chars().count())
+            .collect();
+        assert_eq!(breaks, expected);
+
+        // Invalid scalar values are treated as U+FFFD.
+        let input = [0x61, 0xd800, 0x20, 0x110000, 0x28, 0xdfff];
+        let replaced = [0x61, 0xfffd, 0x20, 0xfffd, 0x28, 0xfffd];
+        let breaks: Vec<usize> = LineBreakIteratorUTF32::new(&input).collect();
+        let expected: Vec<usize> = LineBreakIteratorUTF32::new(&replaced).collect();
+        assert_eq!(breaks, expected);
+        assert_eq!(breaks, [3, 6]);
+        let mut iter = LineBreakIteratorUTF32::new(&input);
+        assert_eq!(iter.next_back(), Some(6));
+        assert_eq!(iter.next_back(), Some(3));
+        assert_eq!(iter.next_back(), None);
+    }
+
+    #[test]
+    fn linebreak_ill_formed_utf8() {
+        // Well-formed input is the same as `LineBreakIterator`.
+        let s = "Hello 世界。$(12.5) ภาษาไทยภาษาไทย 🇯🇵🇺🇸 😀!\r\nEnd";
+        let breaks: Vec<usize> =
+            LineBreakIteratorPotentiallyIllFormedUTF8::new(s.as_bytes()).collect();
+        assert_eq!(breaks, LineBreakIterator::new(s).collect::<Vec<usize>>());
+
+        // Each maximal subpart is U+FFFD, which is AL.
+        let input = b"a \xF0\x90\x80 \xED\xA0\x80b \xC0\xAF \xE0\x80 (\xF4\x90\x80\x80)";
+        // The same text decoded by `String::from_utf8_lossy`.
+        let expected = "a \u{FFFD} \u{FFFD}\u{FFFD}\u{FFFD}b \u{FFFD}\u{FFFD} \u{FFFD}\u{FFFD} (\u{FFFD}\u{FFFD}\u{FFFD}\u{FFFD})";
+        assert_eq!(String::from_utf8_lossy(input), expected);
+        let breaks: Vec<usize> = LineBreakIteratorPotentiallyIllFormedUTF8::new(input).collect();
+        assert_eq!(breaks, [2, 6, 11, 14, 17, 23]);
+        let mut iter = LineBreakIteratorPotentiallyIllFormedUTF8::new(input);
+        let mut reverse = Vec::new();
+        while let Some(b) = iter.next_back() {
+            reverse.push(b);
+        }
+        reverse.reverse();
+        assert_eq!(reverse, breaks);
+
+        // A truncated sequence before Thai.
+        let mut input = b"\xE0\xB8".to_vec();
+        input.extend("ภาษาไทยภาษาไทย".as_bytes());
+        let breaks: Vec<usize> = LineBreakIteratorPotentiallyIllFormedUTF8::new(&input).collect();
+        #[cfg(feature = "lstm")]
+        assert_eq!(breaks, [14, 23, 35, 44]);
+        let expected: Vec<usize> = LineBreakIterator::new("\u{FFFD}ภาษาไทยภาษาไทย")
+            .map(|b| b - 1)
+            .collect();
+        assert_eq!(breaks, expected);
+    }
+
+    #[test]
+    fn linebreak_lone_surrogates() {
+        let thai: Vec<u16> = "ภาษาไทยภาษาไทย".encode_utf16().collect();
+        let inputs = [
+            // Lone surrogates before, inside and after Thai.
+            [&[0xd800][..], &thai[..7], &[0xdc00], &thai[7..], &[0xd83d]].concat(),
+            // Reversed surrogate pair around Thai.
+            [
+                &[0xdc00][..],
+                &[0xd800],
+                &thai,
+                &[0xde00],
+                &[0x20],
+                &[0xd83d],
+            ]
+            .concat(),
+            // A high surrogate followed by Thai, and a low surrogate after a space.
+            [&thai[..4], &[0xd83d], &thai[4..], &[0x20, 0xde00, 0x61]].concat(),
+        ];
+        for input in inputs.iter() {
+            let replaced: Vec<u16> = core::char::decode_utf16(input.iter().cloned())
+                .map(|r| r.unwrap_or(core::char::REPLACEMENT_CHARACTER))
+                .collect::<String>()
+                .encode_utf16()
+                .collect();
+            assert_eq!(input.len(), replaced.len());
+            let expected: Vec<usize> = LineBreakIteratorUTF16::new(&replaced).collect();
+            let breaks: Vec<usize> = LineBreakIteratorUTF16::new(input).collect();
+            assert_eq!(breaks, expected, "{:x?}", input);
+            let mut reverse: Vec<usize> = LineBreakIteratorUTF16::new(input).rev().collect();
+            reverse.reverse();
+            assert_eq!(reverse, expected, "{:x?}", input);
+        }
+
+        #[cfg(feature = "lstm")]
+        {
+            let breaks: Vec<usize> = LineBreakIteratorUTF16::new(&inputs[0]).collect();
+            assert_eq!(breaks, [5, 8, 13, 16, 17]);
+        }
+    }
+
+    #[test]
+    fn linebreak_reader() {
+        let s = "$(12) 3,4 abc\r\nกากกา ภาษาไทยภาษาไทย 🇯🇵🇺🇸 日本語。";
+        let expected: Vec<(usize, BreakKind)> =
+            LineBreakIterator::new(s).with_break_kind().collect();
+        for capacity in 1..8 {
+            let reader = std::io::BufReader::with_capacity(capacity, s.as_bytes());
+            let breaks: Vec<(usize, BreakKind)> =
+                LineBreakReader::new(reader).map(|b| b.unwrap()).collect();
+            assert_eq!(breaks, expected, "capacity {}", capacity);
+
+            let reader = std::io::BufReader::with_capacity(capacity, s.as_bytes());
+            let segments: Vec<(usize, String, BreakKind)> =
+                LineSegmentReader::new(reader).map(|s| s.unwrap()).collect();
+            let mut start = 0;
+            for (segment, &(end, kind)) in segments.iter().zip(expected.iter()) {
+                assert_eq!(*segment, (start, s[start..end].to_string(), kind));
+                start = end;
+            }
+            assert_eq!(segments.len(), expected.len());
+        }
+
+        // Invalid and incomplete UTF-8 are errors.
+        for input in [&b"abc \xff def"[..], &b"abc \xe3\x81"[..]].iter() {
+            let reader = std::io::BufReader::with_capacity(2, *input);
+            let result: Vec<std::io::Result<(usize, BreakKind)>> =
+                LineBreakReader::new(reader).collect();
+            // The break after the space isn't confirmed before the error.
+            assert_eq!(result.len(), 1);
+            assert_eq!(
+                result[0].as_ref().unwrap_err().kind(),
+                std::io::ErrorKind::InvalidData
+            );
+        }
+    }
+
+    #[test]
+    fn linebreak_reader_lookahead() {
+        // Complex language text and digits have no point where the breaks are settled. When they
+        // are shorter than the limit, the reader gives the same breaks as the iterator.
+        let s = format!(
+            "{} {} {} ภาษาไทย {}",
+            "1".repeat(10_000),
+            "ສະບາຍດີ".repeat(500),
+            "ភាសាខ្មែរ".repeat(500),
+            "မြန်မာ".repeat(500)
+        );
+        assert!(s.len() < DEFAULT_MAX_LOOKAHEAD);
+        let expected: Vec<(usize, BreakKind)> =
+            LineBreakIterator::new(&s).with_break_kind().collect();
+        for capacity in [7, 64, 4096].iter() {
+            let reader = std::io::BufReader::with_capacity(*capacity, s.as_bytes());
+            let breaks: Vec<(usize, BreakKind)> =
+                LineBreakReader::new(reader).map(|b| b.unwrap()).collect();
+            assert!(breaks == expected, "capacity {}", capacity);
+        }
+    }
+
+    #[test]
+    fn linebreak_segments() {
+        let s = "Hello,\tworld  \r\n日本語\r\u{85}end ";
+        let segments: Vec<LineSegment> = line_break_segments(s).collect();
+        let expected = [
+            ("Hello,\t", BreakKind::Allowed, 6..7),
+            ("world  \r\n", BreakKind::Mandatory, 12..16),
+            ("日", BreakKind::Allowed, 19..19),
+            ("本", BreakKind::Allowed, 22..22),
+            ("語\r", BreakKind::Mandatory, 25..26),
+            ("\u{85}", BreakKind::Mandatory, 26..28),
+            ("end ", BreakKind::Allowed, 31..32),
+        ];
+        assert_eq!(segments.len(), expected.len());
+        let mut start = 0;
+        for (segment, (text, kind, trailing_whitespace)) in segments.iter().zip(expected.iter()) {
+            assert_eq!(segment.range, start..start + text.len());
+            assert_eq!(segment.text, *text);
+            assert_eq!(segment.kind, *kind);
+            assert_eq!(segment.trailing_whitespace, *trailing_whitespace);
+            start += text.len();
+        }
+
+        // Segments start from the current position.
+        let mut iter = LineBreakIterator::new(s);
+        iter.next();
+        let segment = iter.segments().next().unwrap();
+        assert_eq!(segment.range, 7..16);
+        assert_eq!(segment.text, "world  \r\n");
+    }
+
+    #[test]
+    fn linebreak_lao() {
+        let s = "ສະບາຍດີ ພາສາລາວ";
+        // Words of the word list, or syllables without it.
+        #[cfg(feature = "dictionaries")]
+        let (expected, expected_utf16) = ([22, 34, 43].to_vec(), [8, 12, 15].to_vec());
+        #[cfg(not(feature = "dictionaries"))]
+        let (expected, expected_utf16) = (
+            [6, 15, 22, 28, 34, 43].to_vec(),
+            [2, 5, 8, 10, 12, 15].to_vec(),
+        );
+        let breaks: Vec<usize> = LineBreakIterator::new(s).collect();
+        assert_eq!(breaks, expected);
+        let mut reverse: Vec<usize> = LineBreakIterator::new(s).rev().collect();
+        reverse.reverse();
+        assert_eq!(reverse, breaks);
+
+        let utf16: Vec<u16> = s.encode_utf16().collect();
+        let breaks: Vec<usize> = LineBreakIteratorUTF16::new(&utf16).collect();
+        assert_eq!(breaks, expected_utf16);
+
+        // Lao digits aren't complex language text, and don't start a line (LB23).
+        let s = "ປີ໒໐໒໐";
+        let breaks: Vec<usize> = LineBreakIterator::new(s).collect();
+        assert_eq!(breaks, [s.len()]);
+    }
+
+    #[test]
+    fn linebreak_khmer() {
+        // "Khmer language is the language of Cambodia"
+        let s = "ភាសាខ្មែរ ជាភាសានៃកម្ពុជា";
+        // Words of the word list, or clusters without it.
+        #[cfg(feature = "dictionaries")]
+        let (expected, first) = ([10, 12, 16, 18, 25].to_vec(), "ភាសាខ្មែរ ");
+        #[cfg(not(feature = "dictionaries"))]
+        let (expected, first) = ([2, 4, 10, 12, 14, 16, 19, 23, 25].to_vec(), "ភា");
+        let utf16: Vec<u16> = s.encode_utf16().collect();
+        let breaks: Vec<usize> = LineBreakIteratorUTF16::new(&utf16).collect();
+        assert_eq!(breaks, expected);
+        // No break after COENG.
+        assert!(breaks.iter().all(|&b| utf16[b - 1] != 0x17d2));
+        let mut reverse: Vec<usize> = LineBreakIteratorUTF16::new(&utf16).rev().collect();
+        reverse.reverse();
+        assert_eq!(reverse, breaks);
+
+        let expected: Vec<usize> = breaks
+            .iter()
+            .map(|&b| String::from_utf16(&utf16[..b]).unwrap().len())
+            .collect();
+        let breaks: Vec<usize> = LineBreakIterator::new(s).collect();
+        assert_eq!(breaks, expected);
+        assert_eq!(&s[..breaks[0]], first);
+        let mut reverse: Vec<usize> = LineBreakIterator::new(s).rev().collect();
+        reverse.reverse();
+        assert_eq!(reverse, breaks);
+    }
+
+    #[test]
+    fn linebreak_myanmar() {
+        // "Hello, the world" with a stacked consonant in "world".
+        let s = "မင်္ဂလာပါ ကမ္ဘာလောက။";
+        let breaks: Vec<usize> = LineBreakIterator::new(s).collect();
+        let segments: Vec<&str> = [0]
+            .iter()
+            .chain(breaks.iter())
+            .zip(breaks.iter())
+            .map(|(&start, &end)| &s[start..end])
+            .collect();
+        // No break before the space (LB7), or before the section mark, which is BA (LB21).
+        assert_eq!(segments, ["မင်္ဂ", "လာ", "ပါ ", "ကမ္ဘာ", "လော", "က။"]);
+        let mut reverse: Vec<usize> = LineBreakIterator::new(s).rev().collect();
+        reverse.reverse();
+        assert_eq!(reverse, breaks);
+
+        let utf16: Vec<u16> = s.encode_utf16().collect();
+        let expected: Vec<usize> = breaks
+            .iter()
+            .map(|&b| s[..b].encode_utf16().count())
+            .collect();
+        let breaks: Vec<usize> = LineBreakIteratorUTF16::new(&utf16).collect();
+        assert_eq!(breaks, expected);
+        // No break around VIRAMA.
+        assert!(breaks
+            .iter()
+            .all(|&b| utf16[b - 1] != 0x1039 && utf16.get(b) != Some(&0x1039)));
+
+        // The end of the run is resolved by the pair rules.
+        let trace = LineBreakIterator::new(s).trace_rules();
+        let last = trace.last().unwrap();
+        assert_eq!(
+            (last.decision, last.left_class, last.right_class, last.rule),
+            (BreakDecision::Prohibited, "SA", "BA", "LB21")
+        );
+    }
+
+    #[test]
+    fn linebreak_dictionary() {
+        // "Thai language is easy" broken by a dictionary.
+        let s = "ภาษาไทยง่าย ok";
+        let dictionary = Dictionary::new(["ภาษา", "ไทย", "ง่าย"].iter().cloned());
+        let options = LineBreakOptions::new().dictionary(ComplexScript::Thai, Arc::new(dictionary));
+        let breaks: Vec<usize> = LineBreakIterator::new_with_options(s, &options).collect();
+        assert_eq!(breaks, [12, 21, 34, 36]);
+        let mut reverse: Vec<usize> = LineBreakIterator::new_with_options(s, &options)
+            .rev()
+            .collect();
+        reverse.reverse();
+        assert_eq!(reverse, breaks);
+
+        let utf16: Vec<u16> = s.encode_utf16().collect();
+        let breaks: Vec<usize> =
+            LineBreakIteratorUTF16::new_with_options(&utf16, &options).collect();
+        assert_eq!(breaks, [4, 7, 12, 14]);
+
+        // Unknown text is kept together, and other scripts use the built-in segmenters.
+        let dictionary = Dictionary::new(["ไทย"].iter().cloned());
+        let options = LineBreakOptions::new().dictionary(ComplexScript::Thai, Arc::new(dictionary));
+        let breaks: Vec<usize> =
+            LineBreakIterator::new_with_options("ภาษาไทย ສະບາຍດີ", &options).collect();
+        #[cfg(feature = "dictionaries")]
+        assert_eq!(breaks, [12, 22, 43]);
+        #[cfg(not(feature = "dictionaries"))]
+        assert_eq!(breaks, [12, 22, 28, 37, 43]);
+    }
+
+    #[test]
+    fn linebreak_complex_segmenter() {
+        // Break between every two characters, with offsets out of the run and out of order.
+        struct CharSegmenter;
+
+        impl ComplexSegmenter for CharSegmenter {
+            fn segment_utf16(&self, input: &[u16]) -> Vec<usize> {
+                let mut breaks: Vec<usize> = (0..=input.len() + 1).rev().collect();
+                breaks.push(2);
+                breaks
+            }
+        }
+
+        let s = "ไทย ລາວ";
+        let options =
+            LineBreakOptions::new().complex_segmenter(ComplexScript::Thai, Arc::new(CharSegmenter));
+        let breaks: Vec<usize> = LineBreakIterator::new_with_options(s, &options).collect();
+        assert_eq!(breaks, [3, 6, 10, 19]);
+        let mut reverse: Vec<usize> = LineBreakIterator::new_with_options(s, &options)
+            .rev()
+            .collect();
+        reverse.reverse();
+        assert_eq!(reverse, breaks);
+        let utf16: Vec<u16> = s.encode_utf16().collect();
+        let breaks: Vec<usize> =
+            LineBreakIteratorUTF16::new_with_options(&utf16, &options).collect();
+        assert_eq!(breaks, [1, 2, 4, 7]);
+
+        // Each script has its own segmenter.
+        let options = options.complex_segmenter(ComplexScript::Lao, Arc::new(CharSegmenter));
+        let breaks: Vec<usize> = LineBreakIterator::new_with_options(s, &options).collect();
+        assert_eq!(breaks, [3, 6, 10, 13, 16, 19]);
+
+        // The last segmenter of a script is used.
+        let dictionary = Dictionary::new(["ไทย"].iter().cloned());
+        let options = options.dictionary(ComplexScript::Thai, Arc::new(dictionary));
+        let breaks: Vec<usize> = LineBreakIterator::new_with_options(s, &options).collect();
+        assert_eq!(breaks, [10, 13, 16, 19]);
+    }
 }
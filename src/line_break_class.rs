@@ -0,0 +1,235 @@
+use crate::lb_define::*;
+use crate::line_breaker::get_linebreak_property_utf32_with_rule;
+use crate::BreakRules;
+use crate::LineBreakOptions;
+
+/// The Line_Break property value of a code point, as defined in
+/// [Unicode Standard Annex #14](http://www.unicode.org/reports/tr14/#Table1).
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum LineBreakClass {
+    /// AI
+    Ambiguous,
+    /// AL
+    Alphabetic,
+    /// B2
+    BreakBoth,
+    /// BA
+    BreakAfter,
+    /// BB
+    BreakBefore,
+    /// BK
+    MandatoryBreak,
+    /// CB
+    ContingentBreak,
+    /// CJ
+    ConditionalJapaneseStarter,
+    /// CL
+    ClosePunctuation,
+    /// CM
+    CombiningMark,
+    /// CP
+    CloseParenthesis,
+    /// CR
+    CarriageReturn,
+    /// EB
+    EBase,
+    /// EM
+    EModifier,
+    /// EX
+    Exclamation,
+    /// GL
+    Glue,
+    /// H2
+    H2,
+    /// H3
+    H3,
+    /// HL
+    HebrewLetter,
+    /// HY
+    Hyphen,
+    /// ID
+    Ideographic,
+    /// IN
+    Inseparable,
+    /// IS
+    InfixNumeric,
+    /// JL
+    JL,
+    /// JT
+    JT,
+    /// JV
+    JV,
+    /// LF
+    LineFeed,
+    /// NL
+    NextLine,
+    /// NS
+    Nonstarter,
+    /// NU
+    Numeric,
+    /// OP
+    OpenPunctuation,
+    /// PO
+    PostfixNumeric,
+    /// PR
+    PrefixNumeric,
+    /// QU
+    Quotation,
+    /// RI
+    RegionalIndicator,
+    /// SA
+    ComplexContext,
+    /// SG
+    Surrogate,
+    /// SP
+    Space,
+    /// SY
+    BreakSymbols,
+    /// WJ
+    WordJoiner,
+    /// XX
+    Unknown,
+    /// ZW
+    ZWSpace,
+    /// ZWJ
+    ZWJ,
+}
+
+impl LineBreakClass {
+    // Convert a class of lb_define. The states of the rule table are never returned by the
+    // property table.
+    pub(crate) fn from_property(prop: u8) -> LineBreakClass {
+        match prop {
+            AI => LineBreakClass::Ambiguous,
+            AL => LineBreakClass::Alphabetic,
+            B2 => LineBreakClass::BreakBoth,
+            BA => LineBreakClass::BreakAfter,
+            BB => LineBreakClass::BreakBefore,
+            BK => LineBreakClass::MandatoryBreak,
+            CB => LineBreakClass::ContingentBreak,
+            CJ => LineBreakClass::ConditionalJapaneseStarter,
+            CL => LineBreakClass::ClosePunctuation,
+            CM => LineBreakClass::CombiningMark,
+            CP => LineBreakClass::CloseParenthesis,
+            CR => LineBreakClass::CarriageReturn,
+            EB => LineBreakClass::EBase,
+            EM => LineBreakClass::EModifier,
+            EX => LineBreakClass::Exclamation,
+            GL => LineBreakClass::Glue,
+            H2 => LineBreakClass::H2,
+            H3 => LineBreakClass::H3,
+            HL => LineBreakClass::HebrewLetter,
+            HY => LineBreakClass::Hyphen,
+            ID => LineBreakClass::Ideographic,
+            IN => LineBreakClass::Inseparable,
+            IS => LineBreakClass::InfixNumeric,
+            JL => LineBreakClass::JL,
+            JT => LineBreakClass::JT,
+            JV => LineBreakClass::JV,
+            LF => LineBreakClass::LineFeed,
+            NL => LineBreakClass::NextLine,
+            NS => LineBreakClass::Nonstarter,
+            NU => LineBreakClass::Numeric,
+            OP_EA | OP_OP30 => LineBreakClass::OpenPunctuation,
+            PO => LineBreakClass::PostfixNumeric,
+            PR => LineBreakClass::PrefixNumeric,
+            QU => LineBreakClass::Quotation,
+            RI => LineBreakClass::RegionalIndicator,
+            SA => LineBreakClass::ComplexContext,
+            SG => LineBreakClass::Surrogate,
+            SP => LineBreakClass::Space,
+            SY => LineBreakClass::BreakSymbols,
+            WJ => LineBreakClass::WordJoiner,
+            ZW => LineBreakClass::ZWSpace,
+            ZWJ => LineBreakClass::ZWJ,
+            _ => LineBreakClass::Unknown,
+        }
+    }
+}
+
+/// Return the Line_Break property of a character.
+pub fn line_break_class(c: char) -> LineBreakClass {
+    line_break_class_with_rule(c, BreakRules::default())
+}
+
+/// Return the line break class of a character that the line breaker uses with CSS rules.
+///
+/// For example, CJ is resolved to ID unless `line-break: strict;` is used.
+pub fn line_break_class_with_rule(c: char, rules: BreakRules) -> LineBreakClass {
+    LineBreakClass::from_property(get_linebreak_property_utf32_with_rule(
+        c as u32,
+        rules.line_break_rule,
+        rules.word_break_rule,
+        rules.ja_zh,
+    ))
+}
+
+/// Return the line break class of a character that the line breaker uses with `options`.
+///
+/// ```rust
+/// use uax14_rs::{line_break_class_with_options, LineBreakClass, LineBreakOptions, LineBreakRule};
+///
+/// let options = LineBreakOptions::new().language("ja");
+/// assert_eq!(
+///     line_break_class_with_options('\u{3041}', &options),
+///     LineBreakClass::ConditionalJapaneseStarter
+/// );
+/// let options = options.line_break_rule(LineBreakRule::Normal);
+/// assert_eq!(line_break_class_with_options('\u{3041}', &options), LineBreakClass::Ideographic);
+/// ```
+pub fn line_break_class_with_options(c: char, options: &LineBreakOptions) -> LineBreakClass {
+    line_break_class_with_rule(c, options.get_break_rules())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::line_break_class;
+    use crate::line_break_class_with_options;
+    use crate::line_break_class_with_rule;
+    use crate::BreakRules;
+    use crate::LineBreakClass;
+    use crate::LineBreakOptions;
+    use crate::LineBreakRule;
+    use crate::WordBreakRule;
+
+    #[test]
+    fn linebreak_class() {
+        assert_eq!(line_break_class('a'), LineBreakClass::Alphabetic);
+        assert_eq!(line_break_class(' '), LineBreakClass::Space);
+        assert_eq!(line_break_class('('), LineBreakClass::OpenPunctuation);
+        assert_eq!(
+            line_break_class('\u{FF08}'),
+            LineBreakClass::OpenPunctuation
+        );
+        assert_eq!(line_break_class('\u{0E01}'), LineBreakClass::ComplexContext);
+        assert_eq!(line_break_class('\u{20000}'), LineBreakClass::Ideographic);
+        assert_eq!(line_break_class('\u{50005}'), LineBreakClass::Unknown);
+        assert_eq!(
+            line_break_class('\u{3041}'),
+            LineBreakClass::ConditionalJapaneseStarter
+        );
+        let rules = BreakRules {
+            line_break_rule: LineBreakRule::Normal,
+            ..Default::default()
+        };
+        assert_eq!(
+            line_break_class_with_rule('\u{3041}', rules),
+            LineBreakClass::Ideographic
+        );
+
+        let options = LineBreakOptions::new().word_break_rule(WordBreakRule::BreakAll);
+        assert_eq!(
+            line_break_class_with_options('\u{3041}', &options),
+            LineBreakClass::Ideographic
+        );
+        let options = LineBreakOptions::new().language("zh-Hant");
+        assert_eq!(
+            line_break_class_with_options('\u{3041}', &options),
+            LineBreakClass::ConditionalJapaneseStarter
+        );
+        assert_eq!(
+            line_break_class_with_options('\u{B0}', &options),
+            line_break_class_with_rule('\u{B0}', options.get_break_rules())
+        );
+    }
+}
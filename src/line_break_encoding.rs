@@ -0,0 +1,269 @@
+extern crate encoding_rs;
+
+use crate::LineBreakIterator;
+use crate::LineBreakOptions;
+use encoding_rs::{CoderResult, Decoder, Encoding};
+
+// The number of bytes decoded at a time. The offsets in the original bytes are found again from
+// the start of a chunk when they are needed.
+const CHUNK_LEN: usize = 1024;
+
+fn decode_to_string(decoder: &mut Decoder, src: &[u8], dst: &mut String, last: bool) {
+    dst.reserve(decoder.max_utf8_buffer_length(src.len()).unwrap());
+    let (result, _, _) = decoder.decode_to_string(src, dst, last);
+    debug_assert_eq!(result, CoderResult::InputEmpty);
+}
+
+/// Text decoded from any encoding of the WHATWG Encoding Standard. The text is decoded in chunks,
+/// and the offset in the original bytes of a byte of the decoded text is found by decoding its
+/// chunk again.
+pub struct DecodedText<'a> {
+    input: &'a [u8],
+    encoding: &'static Encoding,
+    text: String,
+    // The offsets in the decoded text and in the original bytes where a new decoder decodes the
+    // following bytes to the same text. They are sorted by both offsets.
+    checkpoints: Vec<(usize, usize)>,
+}
+
+impl<'a> DecodedText<'a> {
+    /// Decode `input` by `encoding`. A BOM isn't removed, and malformed sequences are replaced
+    /// with U+FFFD.
+    pub fn new(input: &'a [u8], encoding: &'static Encoding) -> DecodedText<'a> {
+        let mut decoder = encoding.new_decoder_without_bom_handling();
+        let mut text = String::with_capacity(input.len());
+        let mut checkpoints = vec![(0, 0)];
+        for (i, chunk) in input.chunks(CHUNK_LEN).enumerate() {
+            let start = i * CHUNK_LEN;
+            let last = start + chunk.len() == input.len();
+            let len = text.len();
+            if start == 0 {
+                decode_to_string(&mut decoder, chunk, &mut text, last);
+                continue;
+            }
+            // A new decoder may differ from the current one, such as when a character or an
+            // escape sequence of ISO-2022-JP continues from the previous chunk. It is used from
+            // here only when it decodes this chunk to the same text.
+            let mut new_decoder = encoding.new_decoder_without_bom_handling();
+            let mut new_text = String::new();
+            decode_to_string(&mut new_decoder, chunk, &mut new_text, last);
+            decode_to_string(&mut decoder, chunk, &mut text, last);
+            if text[len..] == new_text {
+                decoder = new_decoder;
+                checkpoints.push((len, start));
+            }
+        }
+        DecodedText {
+            input,
+            encoding,
+            text,
+            checkpoints,
+        }
+    }
+
+    /// Return the decoded text.
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// Return the offset in the original bytes of `offset` in the decoded text.
+    pub fn get_original_offset(&self, offset: usize) -> usize {
+        if offset == self.text.len() {
+            return self.input.len();
+        }
+        let index = self.get_checkpoint(offset);
+        self.get_chunk_offsets(index)[offset - self.checkpoints[index].0]
+    }
+
+    // Return the index of the last checkpoint at or before `offset` in the decoded text.
+    fn get_checkpoint(&self, offset: usize) -> usize {
+        self.checkpoints.partition_point(|c| c.0 <= offset) - 1
+    }
+
+    // Return the offset in the original bytes of each byte of the decoded text from the
+    // checkpoint `index` to the next one.
+    fn get_chunk_offsets(&self, index: usize) -> Vec<usize> {
+        let (text_start, input_start) = self.checkpoints[index];
+        let (text_end, input_end) = match self.checkpoints.get(index + 1) {
+            Some(&checkpoint) => checkpoint,
+            None => (self.text.len(), self.input.len()),
+        };
+        let mut decoder = self.encoding.new_decoder_without_bom_handling();
+        let mut text = String::with_capacity(text_end - text_start);
+        let mut offsets = Vec::with_capacity(text_end - text_start);
+        // The start of the bytes that haven't been decoded to any character yet.
+        let mut start = input_start;
+        // Feed one byte at a time to know which bytes each character comes from.
+        for i in input_start..input_end {
+            let len = text.len();
+            let last = i + 1 == self.input.len();
+            decode_to_string(&mut decoder, &self.input[i..i + 1], &mut text, last);
+            for (index, c) in text[len..].char_indices() {
+                // The first character starts from the bytes pending in the decoder. The others
+                // come from this byte, such as ASCII after a broken lead byte.
+                let offset = if index == 0 { start } else { i };
+                offsets.resize(offsets.len() + c.len_utf8(), offset);
+            }
+            if text.len() > len {
+                start = i + 1;
+            }
+        }
+        debug_assert_eq!(text, &self.text[text_start..text_end]);
+        offsets
+    }
+}
+
+/// Line break iterator for text in a legacy encoding. The offsets are the ones of the original
+/// bytes.
+///
+/// ```rust
+/// use uax14_rs::{DecodedText, LineBreakIteratorEncoded};
+///
+/// let text = DecodedText::new(b"caf\xe9 au lait", encoding_rs::WINDOWS_1252);
+/// let breaks: Vec<usize> = LineBreakIteratorEncoded::new(&text).collect();
+/// assert_eq!(breaks, [5, 8, 12]);
+/// ```
+pub struct LineBreakIteratorEncoded<'a> {
+    iter: LineBreakIterator<'a>,
+    text: &'a DecodedText<'a>,
+    // The checkpoint of the last break and the offsets until the next one, which the following
+    // breaks are likely in.
+    chunk: Option<(usize, Vec<usize>)>,
+}
+
+impl<'a> LineBreakIteratorEncoded<'a> {
+    /// Create line break iterator for decoded text
+    pub fn new(input: &'a DecodedText<'a>) -> LineBreakIteratorEncoded<'a> {
+        LineBreakIteratorEncoded::new_with_options(input, &LineBreakOptions::default())
+    }
+
+    /// Create line break iterator with options for decoded text
+    pub fn new_with_options(
+        input: &'a DecodedText<'a>,
+        options: &LineBreakOptions,
+    ) -> LineBreakIteratorEncoded<'a> {
+        LineBreakIteratorEncoded {
+            iter: LineBreakIterator::new_with_options(&input.text, options),
+            text: input,
+            chunk: None,
+        }
+    }
+
+    fn get_original_offset(&mut self, offset: usize) -> usize {
+        if offset == self.text.text.len() {
+            return self.text.input.len();
+        }
+        let index = self.text.get_checkpoint(offset);
+        if self.chunk.as_ref().map(|chunk| chunk.0) != Some(index) {
+            self.chunk = Some((index, self.text.get_chunk_offsets(index)));
+        }
+        let offsets = &self.chunk.as_ref().unwrap().1;
+        offsets[offset - self.text.checkpoints[index].0]
+    }
+}
+
+impl<'a> Iterator for LineBreakIteratorEncoded<'a> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let offset = self.iter.next()?;
+        Some(self.get_original_offset(offset))
+    }
+}
+
+impl<'a> DoubleEndedIterator for LineBreakIteratorEncoded<'a> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let offset = self.iter.next_back()?;
+        Some(self.get_original_offset(offset))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::DecodedText;
+    use crate::LineBreakIterator;
+    use crate::LineBreakIteratorEncoded;
+    use encoding_rs::{Encoding, EUC_KR, GB18030, ISO_2022_JP, SHIFT_JIS, UTF_8, WINDOWS_1252};
+
+    // Return the breaks of `s` in the offsets of `s` encoded by `encoding`.
+    fn get_expected_breaks(s: &str, encoding: &'static Encoding) -> Vec<usize> {
+        LineBreakIterator::new(s)
+            .map(|b| encoding.encode(&s[..b]).0.len())
+            .collect()
+    }
+
+    #[test]
+    fn linebreak_encoded() {
+        let tests: [(&str, &'static Encoding); 3] = [
+            ("日本語のテキスト。Hello world (テスト)", SHIFT_JIS),
+            ("한국어 텍스트입니다. 1,000원", EUC_KR),
+            ("Ça va? «très bien» 10€", WINDOWS_1252),
+        ];
+        for (s, encoding) in tests.iter() {
+            let (bytes, _, had_errors) = encoding.encode(s);
+            assert!(!had_errors);
+            let text = DecodedText::new(&bytes, encoding);
+            assert_eq!(text.as_str(), *s);
+            let breaks: Vec<usize> = LineBreakIteratorEncoded::new(&text).collect();
+            assert_eq!(breaks, get_expected_breaks(s, encoding), "{}", s);
+            let mut reverse: Vec<usize> = LineBreakIteratorEncoded::new(&text).rev().collect();
+            reverse.reverse();
+            assert_eq!(reverse, breaks, "{}", s);
+        }
+
+        let text = DecodedText::new(b"\x93\xfa\x96\x7b\x8c\xea", SHIFT_JIS);
+        let breaks: Vec<usize> = LineBreakIteratorEncoded::new(&text).collect();
+        assert_eq!(breaks, [2, 4, 6]);
+
+        // A break before an escape sequence is at the start of the escape sequence.
+        let (bytes, _, _) = ISO_2022_JP.encode("日本語 text、テキスト");
+        let text = DecodedText::new(&bytes, ISO_2022_JP);
+        let breaks: Vec<usize> = LineBreakIteratorEncoded::new(&text).collect();
+        assert_eq!(breaks, [5, 7, 13, 22, 24, 26, 28, 33]);
+    }
+
+    #[test]
+    fn linebreak_encoded_chunks() {
+        // Characters and escape sequences cross the chunks. The text is repeated, so the breaks
+        // are the ones of the first repeat, which is in a chunk.
+        let unit = "日本語 text、テキスト。 (1,000) ";
+        let s = unit.repeat(200);
+        let tests: [&'static Encoding; 4] = [SHIFT_JIS, ISO_2022_JP, GB18030, UTF_8];
+        for encoding in tests.iter() {
+            let (unit_bytes, _, _) = encoding.encode(unit);
+            let unit_text = DecodedText::new(&unit_bytes, encoding);
+            let unit_breaks: Vec<usize> = LineBreakIteratorEncoded::new(&unit_text).collect();
+            let unit_len = unit_bytes.len();
+            let expected: Vec<usize> = (0..200)
+                .flat_map(|i| unit_breaks.iter().map(move |b| b + i * unit_len))
+                .collect();
+
+            let (bytes, _, had_errors) = encoding.encode(&s);
+            assert!(!had_errors);
+            assert_eq!(bytes.len(), unit_len * 200);
+            let text = DecodedText::new(&bytes, encoding);
+            assert!(text.as_str() == s, "{}", encoding.name());
+            if *encoding != ISO_2022_JP {
+                assert!(text.checkpoints.len() > 1, "{}", encoding.name());
+            }
+            let breaks: Vec<usize> = LineBreakIteratorEncoded::new(&text).collect();
+            assert!(breaks == expected, "{}", encoding.name());
+            let mut reverse: Vec<usize> = LineBreakIteratorEncoded::new(&text).rev().collect();
+            reverse.reverse();
+            assert!(reverse == breaks, "{}", encoding.name());
+        }
+    }
+
+    #[test]
+    fn linebreak_encoded_malformed() {
+        // A broken lead byte before ASCII is U+FFFD, and ASCII keeps its offset.
+        let text = DecodedText::new(b"\x93\xfa\x81 a\x82", SHIFT_JIS);
+        assert_eq!(text.as_str(), "日\u{FFFD} a\u{FFFD}");
+        assert_eq!(text.get_original_offset(3), 2);
+        assert_eq!(text.get_original_offset(6), 3);
+        assert_eq!(text.get_original_offset(7), 4);
+        assert_eq!(text.get_original_offset(8), 5);
+        let breaks: Vec<usize> = LineBreakIteratorEncoded::new(&text).collect();
+        assert_eq!(breaks, [2, 4, 6]);
+    }
+}
@@ -0,0 +1,262 @@
+use crate::BreakKind;
+use crate::LineBreakOptions;
+use crate::LineBreakStream;
+
+use std::collections::VecDeque;
+use std::io;
+use std::io::BufRead;
+use std::str;
+
+/// The default of `LineBreakReader::max_lookahead`, in bytes.
+pub const DEFAULT_MAX_LOOKAHEAD: usize = 64 * 1024;
+
+/// Line break iterator over text read from `BufRead`. This returns the offsets of break
+/// opportunities in the whole text with their kinds, and keeps only the text that the following
+/// breaks depend on.
+///
+/// The kept text is bounded by `max_lookahead` and the buffer of the reader, so the memory and the
+/// time for each chunk don't grow with the text. A break at the end of the kept text that the
+/// rules don't give is returned as `BreakKind::Forced`.
+///
+/// The text must be UTF-8. Otherwise an error of `io::ErrorKind::InvalidData` is returned.
+///
+/// ```rust
+/// use uax14_rs::{BreakKind, LineBreakReader};
+///
+/// let reader = std::io::BufReader::with_capacity(4, "Hello World".as_bytes());
+/// let breaks: Vec<(usize, BreakKind)> = LineBreakReader::new(reader).map(|b| b.unwrap()).collect();
+/// assert_eq!(breaks, [(6, BreakKind::Allowed), (11, BreakKind::Allowed)]);
+///
+/// let reader = std::io::BufReader::with_capacity(4, "1234567890".as_bytes());
+/// let breaks: Vec<(usize, BreakKind)> = LineBreakReader::new(reader)
+///     .max_lookahead(4)
+///     .map(|b| b.unwrap())
+///     .collect();
+/// assert_eq!(breaks[0], (8, BreakKind::Forced));
+/// assert_eq!(breaks.last(), Some(&(10, BreakKind::Allowed)));
+/// ```
+pub struct LineBreakReader<R> {
+    reader: R,
+    // `None` after the end of the text.
+    stream: Option<LineBreakStream>,
+    breaks: VecDeque<(usize, BreakKind)>,
+    // Bytes that aren't fed to `stream` yet, such as an incomplete UTF-8 sequence.
+    pending: Vec<u8>,
+    // Text after the last returned break, which is kept only for segments.
+    text: Option<String>,
+}
+
+impl<R: BufRead> LineBreakReader<R> {
+    /// Create line break iterator over `reader`
+    pub fn new(reader: R) -> LineBreakReader<R> {
+        LineBreakReader::new_with_options(reader, &LineBreakOptions::default())
+    }
+
+    /// Create line break iterator with options over `reader`
+    pub fn new_with_options(reader: R, options: &LineBreakOptions) -> LineBreakReader<R> {
+        LineBreakReader {
+            reader,
+            stream: Some(
+                LineBreakStream::new_with_options(options).max_lookahead(DEFAULT_MAX_LOOKAHEAD),
+            ),
+            breaks: VecDeque::new(),
+            pending: Vec::new(),
+            text: None,
+        }
+    }
+
+    /// Set the most bytes of text that are kept for the following break opportunities, which is
+    /// `DEFAULT_MAX_LOOKAHEAD` by default. When the text has no point where the breaks are
+    /// settled for longer than this, such as a long run of digits or of complex language text, a
+    /// break is forced at the end of the kept text and returned as `BreakKind::Forced`.
+    pub fn max_lookahead(mut self, max_lookahead: usize) -> LineBreakReader<R> {
+        self.stream = self
+            .stream
+            .map(|stream| stream.max_lookahead(max_lookahead));
+        self
+    }
+
+    // Read the next chunk and feed it to the stream.
+    fn read_chunk(&mut self) -> io::Result<()> {
+        let len = match self.reader.fill_buf() {
+            Ok(buf) => {
+                self.pending.extend_from_slice(buf);
+                buf.len()
+            }
+            Err(ref e) if e.kind() == io::ErrorKind::Interrupted => return Ok(()),
+            Err(e) => return Err(e),
+        };
+        self.reader.consume(len);
+
+        let valid_len = match str::from_utf8(&self.pending) {
+            Ok(s) => s.len(),
+            // The sequence at the end may be completed by the next chunk.
+            Err(e) if e.error_len().is_none() && len > 0 => e.valid_up_to(),
+            Err(_) => {
+                self.stream = None;
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "stream did not contain valid UTF-8",
+                ));
+            }
+        };
+        let chunk = str::from_utf8(&self.pending[..valid_len]).unwrap();
+        if let Some(ref mut text) = self.text {
+            text.push_str(chunk);
+        }
+        if len == 0 {
+            let stream = self.stream.take().unwrap();
+            self.breaks.extend(stream.finish());
+        } else {
+            let stream = self.stream.as_mut().unwrap();
+            self.breaks.extend(stream.feed(chunk));
+        }
+        self.pending.drain(..valid_len);
+        Ok(())
+    }
+}
+
+impl<R: BufRead> Iterator for LineBreakReader<R> {
+    type Item = io::Result<(usize, BreakKind)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(b) = self.breaks.pop_front() {
+                return Some(Ok(b));
+            }
+            self.stream.as_ref()?;
+            if let Err(e) = self.read_chunk() {
+                return Some(Err(e));
+            }
+        }
+    }
+}
+
+/// Iterator over the lines segments of text read from `BufRead`. Each segment is the text between
+/// two break opportunities, and is returned with its offset in the whole text and the kind of the
+/// break after it.
+///
+/// ```rust
+/// use uax14_rs::{BreakKind, LineSegmentReader};
+///
+/// let reader = std::io::BufReader::with_capacity(4, "Hello World".as_bytes());
+/// let segments: Vec<(usize, String, BreakKind)> =
+///     LineSegmentReader::new(reader).map(|s| s.unwrap()).collect();
+/// assert_eq!(
+///     segments,
+///     [
+///         (0, "Hello ".to_string(), BreakKind::Allowed),
+///         (6, "World".to_string(), BreakKind::Allowed)
+///     ]
+/// );
+/// ```
+pub struct LineSegmentReader<R> {
+    iter: LineBreakReader<R>,
+    start: usize,
+}
+
+impl<R: BufRead> LineSegmentReader<R> {
+    /// Create line segment iterator over `reader`
+    pub fn new(reader: R) -> LineSegmentReader<R> {
+        LineSegmentReader::new_with_options(reader, &LineBreakOptions::default())
+    }
+
+    /// Create line segment iterator with options over `reader`
+    pub fn new_with_options(reader: R, options: &LineBreakOptions) -> LineSegmentReader<R> {
+        let mut iter = LineBreakReader::new_with_options(reader, options);
+        iter.text = Some(String::new());
+        LineSegmentReader { iter, start: 0 }
+    }
+
+    /// Set the most bytes of text that are kept for the following break opportunities. See
+    /// `LineBreakReader::max_lookahead`.
+    pub fn max_lookahead(mut self, max_lookahead: usize) -> LineSegmentReader<R> {
+        self.iter = self.iter.max_lookahead(max_lookahead);
+        self
+    }
+}
+
+impl<R: BufRead> Iterator for LineSegmentReader<R> {
+    type Item = io::Result<(usize, String, BreakKind)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (end, kind) = match self.iter.next()? {
+            Ok(b) => b,
+            Err(e) => return Some(Err(e)),
+        };
+        let text = self.iter.text.as_mut().unwrap();
+        let segment: String = text.drain(..end - self.start).collect();
+        let start = self.start;
+        self.start = end;
+        Some(Ok((start, segment, kind)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::BreakKind;
+    use crate::LineBreakReader;
+    use crate::LineSegmentReader;
+    use std::io::BufReader;
+
+    #[test]
+    fn linebreak_reader_bounded() {
+        // Digits have no point where the breaks are settled, so they are kept until the limit.
+        let s = "1".repeat(100_000);
+        let reader = BufReader::with_capacity(16, s.as_bytes());
+        let mut iter = LineBreakReader::new(reader).max_lookahead(1000);
+        let mut breaks = Vec::new();
+        loop {
+            let kept = iter
+                .stream
+                .as_ref()
+                .map_or(0, |stream| stream.buffered_len());
+            assert!(kept <= 1000 + 16, "{}", kept);
+            assert!(iter.pending.len() <= 16);
+            match iter.next() {
+                Some(b) => breaks.push(b.unwrap()),
+                None => break,
+            }
+        }
+        // Only the end of the text is a break of the rules.
+        assert!(breaks.len() > 1);
+        assert_eq!(breaks.last(), Some(&(s.len(), BreakKind::Allowed)));
+        let mut start = 0;
+        for (i, &(b, kind)) in breaks.iter().enumerate() {
+            assert!(b > start && b - start <= 1000 + 16);
+            if i + 1 < breaks.len() {
+                assert_eq!(kind, BreakKind::Forced);
+            }
+            start = b;
+        }
+
+        // The segments are bounded too.
+        let reader = BufReader::with_capacity(16, s.as_bytes());
+        let segments = LineSegmentReader::new(reader).max_lookahead(1000);
+        let mut text = String::new();
+        for segment in segments {
+            let (start, segment, kind) = segment.unwrap();
+            assert_eq!(start, text.len());
+            assert!(segment.len() <= 1000 + 16);
+            text.push_str(&segment);
+            let expected = if text.len() == s.len() {
+                BreakKind::Allowed
+            } else {
+                BreakKind::Forced
+            };
+            assert_eq!(kind, expected);
+        }
+        assert_eq!(text, s);
+
+        // Text with settled breaks isn't changed by the limit.
+        let s = "ab cd ".repeat(1000);
+        let reader = BufReader::with_capacity(16, s.as_bytes());
+        let breaks: Vec<(usize, BreakKind)> = LineBreakReader::new(reader)
+            .max_lookahead(10)
+            .map(|b| b.unwrap())
+            .collect();
+        let expected: Vec<(usize, BreakKind)> =
+            (1..=2000).map(|i| (i * 3, BreakKind::Allowed)).collect();
+        assert_eq!(breaks, expected);
+    }
+}
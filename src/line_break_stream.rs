@@ -0,0 +1,273 @@
+use crate::BreakKind;
+use crate::BreakRules;
+use crate::LineBreakIterator;
+use crate::LineBreakIteratorUTF16;
+use crate::LineBreakOptions;
+use crate::LineBreakRule;
+use crate::WordBreakRule;
+
+/// A line breaker for text that arrives in chunks.
+///
+/// Break opportunities are returned with their kinds as offsets from the start of the whole text
+/// once they can't be changed by the following text. The others are returned by the next `feed`
+/// or `finish`. Only the text after the last confirmed break is kept, and each `feed` scans only
+/// the new text for a point where the breaks are settled.
+///
+/// ```rust
+/// use uax14_rs::{BreakKind, LineBreakStream};
+///
+/// let mut stream = LineBreakStream::new();
+/// let mut breaks = stream.feed("Hello Wo");
+/// breaks.extend(stream.feed("rld, hello"));
+/// breaks.extend(stream.finish());
+/// let breaks: Vec<usize> = breaks.iter().map(|&(b, _)| b).collect();
+/// assert_eq!(breaks, [6, 13, 18]);
+/// ```
+pub struct LineBreakStream {
+    buffer: String,
+    base: usize,
+    // `buffer` has no restart point after its start until this.
+    scanned: usize,
+    max_lookahead: Option<usize>,
+    options: LineBreakOptions,
+}
+
+impl LineBreakStream {
+    /// Create line breaker for chunked text
+    pub fn new() -> LineBreakStream {
+        LineBreakStream::new_with_options(&LineBreakOptions::default())
+    }
+
+    /// Create line breaker for chunked text with CSS rules
+    pub fn new_with_break_rule(
+        line_break_rule: LineBreakRule,
+        word_break_rule: WordBreakRule,
+        ja_zh: bool,
+    ) -> LineBreakStream {
+        let options = LineBreakOptions::from_break_rules(BreakRules {
+            line_break_rule,
+            word_break_rule,
+            ja_zh,
+        });
+        LineBreakStream::new_with_options(&options)
+    }
+
+    /// Create line breaker for chunked text with options
+    pub fn new_with_options(options: &LineBreakOptions) -> LineBreakStream {
+        LineBreakStream {
+            buffer: String::new(),
+            base: 0,
+            scanned: 0,
+            max_lookahead: None,
+            options: options.clone(),
+        }
+    }
+
+    /// Set the most bytes of text that are kept for the following break opportunities. There is
+    /// no limit by default. When the text has no point where the breaks are settled for longer
+    /// than this, such as a long run of digits or of complex language text, a break of
+    /// `BreakKind::Forced` is returned at the end of the kept text.
+    pub fn max_lookahead(mut self, max_lookahead: usize) -> LineBreakStream {
+        self.max_lookahead = Some(max_lookahead);
+        self
+    }
+
+    /// Add the next chunk of text, and return the break opportunities that are confirmed.
+    pub fn feed(&mut self, chunk: &str) -> Vec<(usize, BreakKind)> {
+        self.buffer.push_str(chunk);
+        let last = match self.buffer.char_indices().next_back() {
+            Some((index, _)) => index,
+            None => return Vec::new(),
+        };
+        let iter = LineBreakIterator::new_with_options(&self.buffer, &self.options);
+        let mut result = Vec::new();
+        if let Some(point) = iter.get_last_restart_point(self.scanned, self.buffer.len()) {
+            let base = self.base;
+            result = iter
+                .with_break_kind()
+                .take_while(|&(b, _)| b <= point)
+                .map(|(b, kind)| (b + base, kind))
+                .collect();
+            // The text before the restart point is no longer needed.
+            self.buffer.drain(..point);
+            self.base += point;
+            self.scanned = last - point;
+        } else {
+            self.scanned = last;
+        }
+        if matches!(self.max_lookahead, Some(max) if self.buffer.len() > max) {
+            result.extend(self.flush());
+        }
+        result
+    }
+
+    /// Finish the text, and return the remaining break opportunities.
+    pub fn finish(self) -> Vec<(usize, BreakKind)> {
+        let base = self.base;
+        LineBreakIterator::new_with_options(&self.buffer, &self.options)
+            .with_break_kind()
+            .map(|(b, kind)| (b + base, kind))
+            .collect()
+    }
+
+    // Return the length of the text that is kept for the following break opportunities.
+    #[cfg(test)]
+    pub(crate) fn buffered_len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    // Return the break opportunities of the kept text as if the text ended there, and forget it.
+    // The break at the end of the kept text is forced.
+    fn flush(&mut self) -> Vec<(usize, BreakKind)> {
+        let base = self.base;
+        let mut result: Vec<(usize, BreakKind)> =
+            LineBreakIterator::new_with_options(&self.buffer, &self.options)
+                .with_break_kind()
+                .map(|(b, kind)| (b + base, kind))
+                .collect();
+        if let Some(last) = result.last_mut() {
+            last.1 = BreakKind::Forced;
+        }
+        self.base += self.buffer.len();
+        self.buffer.clear();
+        self.scanned = 0;
+        result
+    }
+}
+
+impl Default for LineBreakStream {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// UTF-16 version of line breaker for chunked text.
+pub struct LineBreakStreamUTF16 {
+    buffer: Vec<u16>,
+    base: usize,
+    // `buffer` has no restart point after its start until this.
+    scanned: usize,
+    max_lookahead: Option<usize>,
+    options: LineBreakOptions,
+}
+
+impl LineBreakStreamUTF16 {
+    /// Create line breaker for chunked UTF-16 text
+    pub fn new() -> LineBreakStreamUTF16 {
+        LineBreakStreamUTF16::new_with_options(&LineBreakOptions::default())
+    }
+
+    /// Create line breaker for chunked UTF-16 text with CSS rules
+    pub fn new_with_break_rule(
+        line_break_rule: LineBreakRule,
+        word_break_rule: WordBreakRule,
+        ja_zh: bool,
+    ) -> LineBreakStreamUTF16 {
+        let options = LineBreakOptions::from_break_rules(BreakRules {
+            line_break_rule,
+            word_break_rule,
+            ja_zh,
+        });
+        LineBreakStreamUTF16::new_with_options(&options)
+    }
+
+    /// Create line breaker for chunked UTF-16 text with options
+    pub fn new_with_options(options: &LineBreakOptions) -> LineBreakStreamUTF16 {
+        LineBreakStreamUTF16 {
+            buffer: Vec::new(),
+            base: 0,
+            scanned: 0,
+            max_lookahead: None,
+            options: options.clone(),
+        }
+    }
+
+    /// Set the most code units of text that are kept for the following break opportunities. See
+    /// `LineBreakStream::max_lookahead`.
+    pub fn max_lookahead(mut self, max_lookahead: usize) -> LineBreakStreamUTF16 {
+        self.max_lookahead = Some(max_lookahead);
+        self
+    }
+
+    // Return the length of the kept text without a high surrogate at the end, which waits for
+    // the low surrogate.
+    fn get_complete_len(&self) -> usize {
+        let end = self.buffer.len();
+        if end > 0 && (self.buffer[end - 1] & 0xfc00) == 0xd800 {
+            return end - 1;
+        }
+        end
+    }
+
+    /// Add the next chunk of text, and return the break opportunities that are confirmed. A
+    /// surrogate pair may be split between chunks.
+    pub fn feed(&mut self, chunk: &[u16]) -> Vec<(usize, BreakKind)> {
+        self.buffer.extend_from_slice(chunk);
+        let end = self.get_complete_len();
+        if end == 0 {
+            return Vec::new();
+        }
+        // The start of the last code point.
+        let mut last = end - 1;
+        if last > 0
+            && (self.buffer[last] & 0xfc00) == 0xdc00
+            && (self.buffer[last - 1] & 0xfc00) == 0xd800
+        {
+            last -= 1;
+        }
+        let iter = LineBreakIteratorUTF16::new_with_options(&self.buffer, &self.options);
+        let mut result = Vec::new();
+        if let Some(point) = iter.get_last_restart_point(self.scanned, end) {
+            let base = self.base;
+            result = iter
+                .with_break_kind()
+                .take_while(|&(b, _)| b <= point)
+                .map(|(b, kind)| (b + base, kind))
+                .collect();
+            // The text before the restart point is no longer needed.
+            self.buffer.drain(..point);
+            self.base += point;
+            self.scanned = last - point;
+        } else {
+            self.scanned = last;
+        }
+        if matches!(self.max_lookahead, Some(max) if self.buffer.len() > max) {
+            result.extend(self.flush());
+        }
+        result
+    }
+
+    /// Finish the text, and return the remaining break opportunities.
+    pub fn finish(self) -> Vec<(usize, BreakKind)> {
+        let base = self.base;
+        LineBreakIteratorUTF16::new_with_options(&self.buffer, &self.options)
+            .with_break_kind()
+            .map(|(b, kind)| (b + base, kind))
+            .collect()
+    }
+
+    // Return the break opportunities of the kept text as if the text ended there, and forget it.
+    // The break at the end of the kept text is forced. A high surrogate at the end is kept.
+    fn flush(&mut self) -> Vec<(usize, BreakKind)> {
+        let end = self.get_complete_len();
+        let base = self.base;
+        let mut result: Vec<(usize, BreakKind)> =
+            LineBreakIteratorUTF16::new_with_options(&self.buffer[..end], &self.options)
+                .with_break_kind()
+                .map(|(b, kind)| (b + base, kind))
+                .collect();
+        if let Some(last) = result.last_mut() {
+            last.1 = BreakKind::Forced;
+        }
+        self.base += end;
+        self.buffer.drain(..end);
+        self.scanned = 0;
+        result
+    }
+}
+
+impl Default for LineBreakStreamUTF16 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,12 +1,15 @@
 extern crate unicode_width;
 
+use crate::complex::*;
+use crate::dictionary::Dictionary;
 use crate::lb_define::*;
-use crate::lstm::*;
 use crate::property_table::*;
 use crate::rule_table::*;
 
 use core::char;
-use core::str::CharIndices;
+use core::fmt;
+use core::ops::Range;
+use std::sync::Arc;
 use unicode_width::UnicodeWidthChar;
 
 #[derive(Copy, Clone, PartialEq)]
@@ -31,7 +34,190 @@ pub enum WordBreakRule {
     KeepAll,
 }
 
-fn get_linebreak_property_utf32_with_rule(
+/// The CSS properties that affect line breaking of a part of the text.
+#[derive(Copy, Clone, PartialEq)]
+pub struct BreakRules {
+    /// `line-break` property
+    pub line_break_rule: LineBreakRule,
+    /// `word-break` property
+    pub word_break_rule: WordBreakRule,
+    /// Whether the content language is Chinese or Japanese
+    pub ja_zh: bool,
+}
+
+impl Default for BreakRules {
+    fn default() -> Self {
+        BreakRules {
+            line_break_rule: LineBreakRule::Strict,
+            word_break_rule: WordBreakRule::Normal,
+            ja_zh: false,
+        }
+    }
+}
+
+/// Options of the line breaker.
+///
+/// ```rust
+/// use uax14_rs::{LineBreakIterator, LineBreakOptions, LineBreakRule};
+///
+/// let options = LineBreakOptions::new()
+///     .line_break_rule(LineBreakRule::Loose)
+///     .language("ja-JP");
+/// let breaks: Vec<usize> = LineBreakIterator::new_with_options("ア\u{30FC}\u{301C}", &options).collect();
+/// assert_eq!(breaks, [3, 6, 9]);
+/// ```
+#[derive(Clone, Default)]
+pub struct LineBreakOptions {
+    rules: BreakRules,
+    language: Option<String>,
+    segmenters: ComplexSegmenters,
+}
+
+impl LineBreakOptions {
+    /// Create options with `line-break: strict;`, `word-break: normal;` and no language
+    pub fn new() -> LineBreakOptions {
+        LineBreakOptions::default()
+    }
+
+    pub(crate) fn from_break_rules(rules: BreakRules) -> LineBreakOptions {
+        LineBreakOptions {
+            rules,
+            language: None,
+            segmenters: ComplexSegmenters::default(),
+        }
+    }
+
+    /// Set `line-break` property
+    pub fn line_break_rule(mut self, line_break_rule: LineBreakRule) -> LineBreakOptions {
+        self.rules.line_break_rule = line_break_rule;
+        self
+    }
+
+    /// Set `word-break` property
+    pub fn word_break_rule(mut self, word_break_rule: WordBreakRule) -> LineBreakOptions {
+        self.rules.word_break_rule = word_break_rule;
+        self
+    }
+
+    /// Set the content language as a BCP-47 language tag such as `ja` or `zh-Hant-TW`.
+    /// The tailorings of `line-break` for Chinese and Japanese are used for them.
+    pub fn language(mut self, language: &str) -> LineBreakOptions {
+        self.rules.ja_zh = is_ja_zh_language(language);
+        self.language = Some(language.to_string());
+        self
+    }
+
+    /// Break the complex language text of `script` by `segmenter` instead of the built-in
+    /// segmenter.
+    pub fn complex_segmenter(
+        mut self,
+        script: ComplexScript,
+        segmenter: Arc<dyn ComplexSegmenter>,
+    ) -> LineBreakOptions {
+        self.segmenters.set_segmenter(script, segmenter);
+        self
+    }
+
+    /// Break the complex language text of `script` by `dictionary` instead of the built-in
+    /// segmenter.
+    pub fn dictionary(
+        self,
+        script: ComplexScript,
+        dictionary: Arc<Dictionary>,
+    ) -> LineBreakOptions {
+        self.complex_segmenter(script, dictionary)
+    }
+
+    /// Return the content language
+    pub fn get_language(&self) -> Option<&str> {
+        self.language.as_deref()
+    }
+
+    /// Return the CSS properties of these options, such as for `breaks_with_rule_runs`.
+    pub fn get_break_rules(&self) -> BreakRules {
+        self.rules
+    }
+}
+
+// Whether the primary language subtag is Chinese or Japanese.
+fn is_ja_zh_language(language: &str) -> bool {
+    let primary = language.split(&['-', '_'][..]).next().unwrap();
+    primary.eq_ignore_ascii_case("ja") || primary.eq_ignore_ascii_case("zh")
+}
+
+/// Return the index of the run that contains `offset`. `runs` are sorted and don't overlap.
+fn find_rule_run(runs: &[(Range<usize>, BreakRules)], offset: usize) -> Option<usize> {
+    runs.binary_search_by(|(range, _)| {
+        if range.end <= offset {
+            core::cmp::Ordering::Less
+        } else if range.start > offset {
+            core::cmp::Ordering::Greater
+        } else {
+            core::cmp::Ordering::Equal
+        }
+    })
+    .ok()
+}
+
+/// The kind of a line break opportunity.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum BreakKind {
+    /// A hard line break after BK, CR, LF or NL (LB4 and LB5).
+    Mandatory,
+    /// An ordinary line wrapping opportunity.
+    Allowed,
+    /// A break that the rules may not allow, which is forced at the end of the text kept by a
+    /// stream or a reader because it reached `max_lookahead`.
+    Forced,
+}
+
+/// An iterator adapter that yields each break opportunity with its [`BreakKind`].
+///
+/// This is created by the `with_break_kind` method of the line break iterators.
+pub struct WithBreakKind<I> {
+    iter: I,
+}
+
+/// The decision at a position between two code points.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum BreakDecision {
+    /// `×`: no break is allowed.
+    Prohibited,
+    /// `÷`: a break is allowed.
+    Allowed,
+    /// `!`: a break is mandatory.
+    Mandatory,
+}
+
+impl fmt::Display for BreakDecision {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BreakDecision::Prohibited => write!(f, "\u{00d7}"),
+            BreakDecision::Allowed => write!(f, "\u{00f7}"),
+            BreakDecision::Mandatory => write!(f, "!"),
+        }
+    }
+}
+
+/// How one position between two code points was resolved by the line breaker.
+///
+/// This is created by the `trace_rules` method of the line break iterators.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RuleTrace {
+    /// The offset of the position.
+    pub offset: usize,
+    /// The break decision.
+    pub decision: BreakDecision,
+    /// The class on the left side. This may be a state of the rule table such as `LB25_NU_IS`.
+    pub left_class: &'static str,
+    /// The class on the right side.
+    pub right_class: &'static str,
+    /// The rule that decided this position such as `LB9`, `LB25` or `LB30a`. `CSS` is used for
+    /// CSS tailoring and `SA` is used for complex language breaking.
+    pub rule: &'static str,
+}
+
+pub(crate) fn get_linebreak_property_utf32_with_rule(
     codepoint: u32,
     line_break_rule: LineBreakRule,
     word_break_rule: WordBreakRule,
@@ -78,12 +264,6 @@ fn get_linebreak_property_utf32_with_rule(
     }
 }
 
-#[inline]
-fn get_linebreak_property_latin1(codepoint: u8) -> u8 {
-    let codepoint = codepoint as usize;
-    UAX14_PROPERTY_TABLE[codepoint / 1024][(codepoint & 0x3ff)]
-}
-
 #[inline]
 fn get_linebreak_property_with_rule(
     codepoint: char,
@@ -221,18 +401,14 @@ fn get_break_state(left: u8, right: u8) -> i8 {
 }
 
 #[inline]
-fn use_complex_breaking_utf32(codepoint: u32) -> bool {
-    // Thai
-    codepoint >= 0xe01 && codepoint <= 0xe7f
+fn get_rule_name(left: u8, right: u8) -> &'static str {
+    UAX14_RULE_NAMES[UAX14_RULE_ID_TABLE[(left as usize) - 1][(right as usize) - 1] as usize]
 }
 
-/*
 #[inline]
 fn use_complex_breaking_utf32(codepoint: u32) -> bool {
-    // Thai, Lao and Khmer
-    (codepoint >= 0xe01 && codepoint <= 0xeff) || (codepoint >= 0x1780 && codepoint <= 0x17ff)
+    get_complex_script(codepoint).is_some()
 }
-*/
 
 macro_rules! break_iterator_impl {
     ($name:ident, $iter_attr:ty, $char_type:ty) => {
@@ -242,35 +418,66 @@ macro_rules! break_iterator_impl {
             len: usize,
             current_pos_data: Option<(usize, $char_type)>,
             result_cache: Vec<usize>,
+            front_break: usize,
+            back_break: Option<usize>,
+            back_cache: Vec<usize>,
             break_rule: LineBreakRule,
             word_break_rule: WordBreakRule,
             ja_zh: bool,
+            segmenters: ComplexSegmenters,
         }
 
         impl<'a> Iterator for $name<'a> {
             type Item = usize;
 
             fn next(&mut self) -> Option<Self::Item> {
+                if let Some(back) = self.back_break {
+                    if self.front_break >= back {
+                        return None;
+                    }
+                }
+                let result = self.next_break()?;
+                if let Some(back) = self.back_break {
+                    if result >= back {
+                        self.front_break = back;
+                        return None;
+                    }
+                }
+                self.front_break = result;
+                Some(result)
+            }
+        }
+
+        impl<'a> DoubleEndedIterator for $name<'a> {
+            fn next_back(&mut self) -> Option<Self::Item> {
+                let result = match self.back_break {
+                    None => self.len,
+                    Some(back) => {
+                        if self.back_cache.is_empty() {
+                            self.back_cache = self.get_breaks_before(back);
+                        }
+                        self.back_cache.pop()?
+                    }
+                };
+                if result <= self.front_break {
+                    self.back_break = Some(self.front_break);
+                    return None;
+                }
+                self.back_break = Some(result);
+                Some(result)
+            }
+        }
+
+        impl<'a> $name<'a> {
+            fn next_break(&mut self) -> Option<usize> {
                 if self.is_eof() {
                     return None;
                 }
 
                 if !self.result_cache.is_empty() {
                     // We have break point cache by previous run.
-                    let mut i = 0;
-                    loop {
-                        if i == *self.result_cache.first().unwrap() {
-                            self.result_cache.remove(0);
-                            self.result_cache = self.result_cache.iter().map(|r| r - i).collect();
-                            return Some(self.current_pos_data.unwrap().0);
-                        }
-                        self.current_pos_data = self.iter.next();
-                        if self.current_pos_data.is_none() {
-                            // Reach EOF
-                            self.result_cache.clear();
-                            return Some(self.len);
-                        }
-                        i += 1;
+                    if let Some(result) = self.next_complex_break() {
+                        return Some(result);
                     }
                 }
 
@@ -338,7 +545,8 @@ macro_rules! break_iterator_impl {
                         if result.is_some() {
                             return result;
                         }
-                        // I may have to fetch text until non-SA character?.
+                        // The end of the run is broken by the pair rules.
+                        continue;
                     }
 
                     // If break_state is equals or grater than 0, it is alias of property.
@@ -388,7 +596,426 @@ macro_rules! break_iterator_impl {
             }
         }
 
+        impl<'a> Iterator for WithBreakKind<$name<'a>> {
+            type Item = (usize, BreakKind);
+
+            fn next(&mut self) -> Option<Self::Item> {
+                let offset = self.iter.next()?;
+                Some((offset, self.iter.get_break_kind(offset)))
+            }
+        }
+
         impl<'a> $name<'a> {
+            /// Wrap this iterator so that it yields each break opportunity with its [`BreakKind`].
+            pub fn with_break_kind(self) -> WithBreakKind<Self> {
+                WithBreakKind { iter: self }
+            }
+
+            /// Return the first break opportunity after `offset`.
+            ///
+            /// This doesn't change the position of this iterator.
+            pub fn following(&self, offset: usize) -> Option<usize> {
+                if offset >= self.len {
+                    return None;
+                }
+                let start = self.get_restart_point(offset);
+                self.get_iterator_at(start).find(|&b| b > offset)
+            }
+
+            /// Return the last break opportunity before `offset`.
+            ///
+            /// This doesn't change the position of this iterator.
+            pub fn preceding(&self, offset: usize) -> Option<usize> {
+                self.get_breaks_before(offset).pop()
+            }
+
+            /// Return whether `offset` is a break opportunity. The start of the text is never
+            /// a break opportunity, and the end of the text always is.
+            ///
+            /// This doesn't change the position of this iterator.
+            pub fn is_boundary(&self, offset: usize) -> bool {
+                let index = match self.iter.char_before(offset) {
+                    Some((index, _)) => index,
+                    None => return false,
+                };
+                let start = self.get_restart_point(index);
+                self.get_iterator_at(start).find(|&b| b >= offset) == Some(offset)
+            }
+
+            /// Return the break opportunities when some parts of the text use other CSS rules,
+            /// such as spans with their own `line-break` and `word-break` properties. `runs` are
+            /// sorted and don't overlap.
+            ///
+            /// A position between two code points of the same run uses the rules of the run.
+            /// The other positions use the rules of this iterator, which should be the ones of
+            /// the nearest common ancestor such as the paragraph.
+            pub fn breaks_with_rule_runs(&self, runs: &[(Range<usize>, BreakRules)]) -> Vec<usize> {
+                let base = BreakRules {
+                    line_break_rule: self.break_rule,
+                    word_break_rule: self.word_break_rule,
+                    ja_zh: self.ja_zh,
+                };
+                // Break opportunities of the whole text by each rule set.
+                let mut rule_breaks: Vec<(BreakRules, Vec<usize>)> = Vec::new();
+                for rules in core::iter::once(base).chain(runs.iter().map(|run| run.1)) {
+                    if rule_breaks.iter().all(|(r, _)| *r != rules) {
+                        let mut iter = self.get_iterator_at(0);
+                        iter.break_rule = rules.line_break_rule;
+                        iter.word_break_rule = rules.word_break_rule;
+                        iter.ja_zh = rules.ja_zh;
+                        rule_breaks.push((rules, iter.collect()));
+                    }
+                }
+
+                let mut candidates: Vec<usize> = rule_breaks
+                    .iter()
+                    .flat_map(|(_, breaks)| breaks.iter().cloned())
+                    .collect();
+                candidates.sort_unstable();
+                candidates.dedup();
+                candidates.retain(|&b| {
+                    let rules = match self.iter.char_before(b) {
+                        Some((index, _)) if b < self.len => {
+                            match (find_rule_run(runs, index), find_rule_run(runs, b)) {
+                                (Some(left), Some(right)) if left == right => runs[left].1,
+                                _ => base,
+                            }
+                        }
+                        _ => base,
+                    };
+                    let (_, breaks) = rule_breaks.iter().find(|(r, _)| *r == rules).unwrap();
+                    breaks.binary_search(&b).is_ok()
+                });
+                candidates
+            }
+
+            /// Return the break opportunities of the edited text from the ones of the text before
+            /// editing. This iterator must be created for the edited text. `edit` is the range of
+            /// the old text that was replaced, and `new_len` is the length of its replacement.
+            ///
+            /// Only the text from the restart point before the edit is scanned, until the breaks
+            /// are the same as the old ones again.
+            pub fn update_breaks(
+                &self,
+                old_breaks: &[usize],
+                edit: Range<usize>,
+                new_len: usize,
+            ) -> Vec<usize> {
+                let new_end = edit.start + new_len;
+                let start = match self.iter.char_before(edit.start) {
+                    Some((index, _)) => self.get_restart_point(index),
+                    None => 0,
+                };
+                let mut result: Vec<usize> = old_breaks
+                    .iter()
+                    .cloned()
+                    .take_while(|&b| b <= start)
+                    .collect();
+                for b in self.get_iterator_at(start) {
+                    result.push(b);
+                    if b <= new_end {
+                        continue;
+                    }
+                    // The break must not be in complex language text that is broken as a whole.
+                    match self.iter.char_before(b) {
+                        Some((_, c)) if !$name::use_complex_breaking(c) => (),
+                        _ => continue,
+                    }
+                    let old_b = b - new_end + edit.end;
+                    if let Ok(i) = old_breaks.binary_search(&old_b) {
+                        result.extend(old_breaks[i + 1..].iter().map(|&b| b - edit.end + new_end));
+                        break;
+                    }
+                }
+                result
+            }
+
+            // Return the break opportunities before `offset` that are after the nearest restart
+            // point having any.
+            fn get_breaks_before(&self, offset: usize) -> Vec<usize> {
+                let mut limit = offset;
+                let mut inclusive = false;
+                loop {
+                    let index = match self.iter.char_before(limit) {
+                        Some((index, _)) => index,
+                        None => return Vec::new(),
+                    };
+                    let start = self.get_restart_point(index);
+                    let breaks: Vec<usize> = self
+                        .get_iterator_at(start)
+                        .take_while(|&b| b < limit || (inclusive && b == limit))
+                        .collect();
+                    if !breaks.is_empty() {
+                        return breaks;
+                    }
+                    // The restart point itself may be a break opportunity.
+                    limit = start;
+                    inclusive = true;
+                }
+            }
+
+            // Return the last restart point after `start` and before the code point that ends at
+            // `end`. The break opportunities until it don't depend on the text after `end`.
+            #[allow(dead_code)]
+            pub(crate) fn get_last_restart_point(&self, start: usize, end: usize) -> Option<usize> {
+                match self.iter.char_before(end) {
+                    Some((index, _)) => self.get_restart_point_after(index, start),
+                    None => None,
+                }
+            }
+
+            // Find a position at or before `offset` where the state machine is always restarted,
+            // so that a new iterator from there returns the same breaks after it.
+            fn get_restart_point(&self, offset: usize) -> usize {
+                self.get_restart_point_after(offset, 0).unwrap_or(0)
+            }
+
+            // The same as `get_restart_point`, but only the positions after `start` are looked at.
+            fn get_restart_point_after(&self, offset: usize, start: usize) -> Option<usize> {
+                let mut iter = self.iter.clone();
+                iter.front_offset = offset;
+                let mut pos = offset;
+                let mut right = iter.next().map(|data| data.1);
+                if right.is_some() {
+                    // `offset` may be in the middle of a surrogate pair.
+                    if let Some((index, c)) = self.iter.char_before(iter.front_offset) {
+                        pos = index;
+                        right = Some(c);
+                    }
+                }
+                while pos > start {
+                    let (index, left) = self.iter.char_before(pos).unwrap();
+                    if let Some(right) = right {
+                        if self.is_restart_point(left, right) {
+                            return Some(pos);
+                        }
+                    }
+                    pos = index;
+                    right = Some(left);
+                }
+                None
+            }
+
+            // The state machine always starts over from `right` in these cases:
+            // - `right` can't be a part of any multi-character state of the rule table.
+            // - After a mandatory break.
+            // - After spaces that aren't followed by SP, CM or ZWJ (LB9, LB14-LB17 and LB8 keep
+            //   their state over them).
+            fn is_restart_point(&self, left: $char_type, right: $char_type) -> bool {
+                if $name::use_complex_breaking(right) {
+                    return false;
+                }
+                let right_prop = self.get_linebreak_property_with_rule(right);
+                match right_prop {
+                    BA | CL | CM | CP | HY | IS | NU | OP_EA | OP_OP30 | RI | SP | SY | ZWJ => (),
+                    _ => return true,
+                }
+                match self.get_linebreak_property_with_rule(left) {
+                    BK | LF | NL => true,
+                    CR => right_prop != LF,
+                    SP => right_prop != SP && right_prop != CM && right_prop != ZWJ,
+                    _ => false,
+                }
+            }
+
+            // Create a new iterator with the same rules, that starts at `offset`.
+            fn get_iterator_at(&self, offset: usize) -> Self {
+                let mut iter = self.iter.clone();
+                iter.front_offset = offset;
+                $name {
+                    iter,
+                    len: self.len,
+                    current_pos_data: None,
+                    result_cache: Vec::new(),
+                    front_break: 0,
+                    back_break: None,
+                    back_cache: Vec::new(),
+                    break_rule: self.break_rule,
+                    word_break_rule: self.word_break_rule,
+                    ja_zh: self.ja_zh,
+                    segmenters: self.segmenters.clone(),
+                }
+            }
+
+            fn from_indices(iter: $iter_attr, len: usize, options: &LineBreakOptions) -> Self {
+                $name {
+                    iter,
+                    len,
+                    current_pos_data: None,
+                    result_cache: Vec::new(),
+                    front_break: 0,
+                    back_break: None,
+                    back_cache: Vec::new(),
+                    break_rule: options.rules.line_break_rule,
+                    word_break_rule: options.rules.word_break_rule,
+                    ja_zh: options.rules.ja_zh,
+                    segmenters: options.segmenters.clone(),
+                }
+            }
+
+            /// Return how every position between two code points is resolved, with the rule
+            /// that decided it. This is intended for debugging unexpected breaks.
+            pub fn trace_rules(self) -> Vec<RuleTrace> {
+                let mut trace: Vec<RuleTrace> = Vec::new();
+                let mut iter = self.iter.clone();
+                let mut left = match iter.next() {
+                    Some(data) => data,
+                    None => return trace,
+                };
+                let mut state: Option<u8> = None;
+                let mut in_complex = false;
+                while let Some(right) = iter.next() {
+                    let right_prop = self.get_linebreak_property_with_rule(right.1);
+                    loop {
+                        let left_prop = match state {
+                            Some(s) => s,
+                            None => self.get_trace_left_property(left.1),
+                        };
+                        let mut rule = None;
+                        if in_complex {
+                            in_complex = $name::use_complex_breaking(right.1);
+                            // The end of the run is broken by the pair rules, except after the
+                            // built-in Thai segmenter.
+                            if in_complex || is_break_after_run(left.1 as u32, &self.segmenters) {
+                                rule = Some("SA");
+                            }
+                        }
+                        if rule.is_none() && state.is_none() {
+                            rule = self.get_css_rule(left.1, right.1, left_prop, right_prop);
+                        }
+                        if rule.is_none()
+                            && state.is_none()
+                            && self.word_break_rule != WordBreakRule::BreakAll
+                            && $name::use_complex_breaking(left.1)
+                            && $name::use_complex_breaking(right.1)
+                        {
+                            in_complex = true;
+                            rule = Some("SA");
+                        }
+                        if let Some(rule) = rule {
+                            state = None;
+                            trace.push(RuleTrace {
+                                offset: right.0,
+                                decision: BreakDecision::Prohibited,
+                                left_class: PROPERTY_NAMES[(left_prop as usize) - 1],
+                                right_class: PROPERTY_NAMES[(right_prop as usize) - 1],
+                                rule,
+                            });
+                            break;
+                        }
+
+                        let break_state = get_break_state(left_prop, right_prop);
+                        if break_state == PREVIOUS_BREAK_RULE {
+                            // LB25 didn't match, so the previous position is resolved by LB31.
+                            if let Some(previous) = trace.last_mut() {
+                                previous.rule = "LB31";
+                            }
+                            state = None;
+                            continue;
+                        }
+                        state = if break_state >= 0 {
+                            Some(break_state as u8)
+                        } else {
+                            None
+                        };
+                        trace.push(RuleTrace {
+                            offset: right.0,
+                            decision: BreakDecision::Prohibited,
+                            left_class: PROPERTY_NAMES[(left_prop as usize) - 1],
+                            right_class: PROPERTY_NAMES[(right_prop as usize) - 1],
+                            rule: get_rule_name(left_prop, right_prop),
+                        });
+                        break;
+                    }
+                    left = right;
+                }
+                if let Some(s) = state {
+                    if get_break_state(s, EOT) == PREVIOUS_BREAK_RULE {
+                        if let Some(previous) = trace.last_mut() {
+                            previous.rule = "LB31";
+                        }
+                    }
+                }
+
+                let mut trace_iter = trace.iter_mut();
+                for (offset, kind) in self.with_break_kind() {
+                    if let Some(t) = trace_iter.by_ref().find(|t| t.offset == offset) {
+                        t.decision = match kind {
+                            BreakKind::Mandatory => BreakDecision::Mandatory,
+                            BreakKind::Allowed | BreakKind::Forced => BreakDecision::Allowed,
+                        };
+                    }
+                }
+                trace
+            }
+
+            fn get_trace_left_property(&self, c: $char_type) -> u8 {
+                let prop = self.get_linebreak_property_with_rule(c);
+                if self.word_break_rule == WordBreakRule::BreakAll {
+                    return match prop {
+                        AL => ID,
+                        NU => ID,
+                        SA => ID,
+                        _ => prop,
+                    };
+                }
+                prop
+            }
+
+            // The same CSS tailoring as `next`, but only reports whether it applies.
+            fn get_css_rule(
+                &self,
+                left: $char_type,
+                right: $char_type,
+                left_prop: u8,
+                right_prop: u8,
+            ) -> Option<&'static str> {
+                if self.word_break_rule == WordBreakRule::KeepAll
+                    && is_non_break_by_keepall(left_prop, right_prop)
+                {
+                    return Some("CSS");
+                }
+                let applied = match self.break_rule {
+                    LineBreakRule::Normal => is_break_utf32_by_normal(right as u32, self.ja_zh),
+                    LineBreakRule::Loose => is_break_utf32_by_loose(
+                        left as u32,
+                        right as u32,
+                        left_prop,
+                        right_prop,
+                        self.ja_zh,
+                    )
+                    .is_some(),
+                    LineBreakRule::Anywhere => true,
+                    _ => false,
+                };
+                if applied {
+                    return Some("CSS");
+                }
+                None
+            }
+
+            // LB4 and LB5: a break after BK, LF, NL, or CR that isn't followed by LF is mandatory.
+            fn get_break_kind(&self, offset: usize) -> BreakKind {
+                let left = match self.iter.char_before(offset) {
+                    Some((_, c)) => c,
+                    None => return BreakKind::Allowed,
+                };
+                match self.get_linebreak_property_with_rule(left) {
+                    BK | LF | NL => BreakKind::Mandatory,
+                    CR => {
+                        let mut iter = self.iter.clone();
+                        iter.front_offset = offset;
+                        match iter.next() {
+                            Some((_, c)) if self.get_linebreak_property_with_rule(c) == LF => {
+                                BreakKind::Allowed
+                            }
+                            _ => BreakKind::Mandatory,
+                        }
+                    }
+                    _ => BreakKind::Allowed,
+                }
+            }
+
             #[inline]
             fn is_eof(&mut self) -> bool {
                 if self.current_pos_data.is_none() {
@@ -421,17 +1048,39 @@ macro_rules! break_iterator_impl {
                 self.iter = start_iter;
                 self.current_pos_data = start_point;
                 let breaks = self.get_line_break_by_platform_fallback(&s);
-                let mut i = 1;
-                self.result_cache = breaks;
+                // The offsets are from `left_codepoint`, but the iterator is at the next one.
+                self.result_cache = breaks.iter().map(|b| b - 1).collect();
+                self.next_complex_break()
+            }
+
+            // Move to the next break of `result_cache`. The last one is the end of the complex
+            // language run, which is left to the pair rules unless `is_break_after_run`: this
+            // returns `None` there, and the iterator is moved back to the last character of the
+            // run.
+            fn next_complex_break(&mut self) -> Option<usize> {
                 // result_cache vector is utf-16 index that is in BMP.
+                let mut i = 0;
+                let mut previous: Option<($iter_attr, Option<(usize, $char_type)>)> = None;
                 loop {
                     if i == *self.result_cache.first().unwrap() {
                         self.result_cache.remove(0);
+                        if self.result_cache.is_empty() {
+                            // The run has two characters at least, so this isn't the first one.
+                            let (previous_iter, previous_pos_data) = previous.unwrap();
+                            let last = previous_pos_data.unwrap().1;
+                            if !is_break_after_run(last as u32, &self.segmenters) {
+                                self.iter = previous_iter;
+                                self.current_pos_data = previous_pos_data;
+                                return None;
+                            }
+                        }
                         self.result_cache = self.result_cache.iter().map(|r| r - i).collect();
                         return Some(self.current_pos_data.unwrap().0);
                     }
+                    previous = Some((self.iter.clone(), self.current_pos_data));
                     self.current_pos_data = self.iter.next();
                     if self.current_pos_data.is_none() {
+                        // Reach EOF
                         self.result_cache.clear();
                         return Some(self.len);
                     }
@@ -442,20 +1091,39 @@ macro_rules! break_iterator_impl {
     };
 }
 
-break_iterator_impl!(LineBreakIterator, CharIndices<'a>, char);
+/// UTF-8 version of line break iterator.
+#[derive(Clone)]
+struct UTF8Indices<'a> {
+    front_offset: usize,
+    iter: &'a str,
+}
+
+impl<'a> Iterator for UTF8Indices<'a> {
+    type Item = (usize, char);
+
+    #[inline]
+    fn next(&mut self) -> Option<(usize, char)> {
+        let ch = self.iter[self.front_offset..].chars().next()?;
+        let index = self.front_offset;
+        self.front_offset += ch.len_utf8();
+        Some((index, ch))
+    }
+}
+
+impl<'a> UTF8Indices<'a> {
+    /// Return the code point that ends at `offset` and its index.
+    fn char_before(&self, offset: usize) -> Option<(usize, char)> {
+        let ch = self.iter[..offset].chars().next_back()?;
+        Some((offset - ch.len_utf8(), ch))
+    }
+}
+
+break_iterator_impl!(LineBreakIterator, UTF8Indices<'a>, char);
 
 impl<'a> LineBreakIterator<'a> {
     /// Create line break iterator
     pub fn new(input: &str) -> LineBreakIterator {
-        LineBreakIterator {
-            iter: input.char_indices(),
-            len: input.len(),
-            current_pos_data: None,
-            result_cache: Vec::new(),
-            break_rule: LineBreakRule::Strict,
-            word_break_rule: WordBreakRule::Normal,
-            ja_zh: false,
-        }
+        LineBreakIterator::new_with_options(input, &LineBreakOptions::default())
     }
 
     /// Create line break iterator with CSS rules
@@ -465,22 +1133,31 @@ impl<'a> LineBreakIterator<'a> {
         word_break_rule: WordBreakRule,
         ja_zh: bool,
     ) -> LineBreakIterator {
-        LineBreakIterator {
-            iter: input.char_indices(),
-            len: input.len(),
-            current_pos_data: None,
-            result_cache: Vec::new(),
-            break_rule: line_break_rule,
+        let options = LineBreakOptions::from_break_rules(BreakRules {
+            line_break_rule,
             word_break_rule,
             ja_zh,
-        }
+        });
+        LineBreakIterator::new_with_options(input, &options)
+    }
+
+    /// Create line break iterator with options
+    pub fn new_with_options(input: &'a str, options: &LineBreakOptions) -> LineBreakIterator<'a> {
+        LineBreakIterator::from_indices(
+            UTF8Indices {
+                front_offset: 0,
+                iter: input,
+            },
+            input.len(),
+            options,
+        )
     }
 
     fn get_linebreak_property(&mut self) -> u8 {
         self.get_linebreak_property_with_rule(self.current_pos_data.unwrap().1)
     }
 
-    fn get_linebreak_property_with_rule(&mut self, c: char) -> u8 {
+    fn get_linebreak_property_with_rule(&self, c: char) -> u8 {
         get_linebreak_property_with_rule(c, self.break_rule, self.word_break_rule, self.ja_zh)
     }
 
@@ -494,7 +1171,7 @@ impl<'a> LineBreakIterator<'a> {
     }
 
     fn get_line_break_by_platform_fallback(&mut self, input: &[u16]) -> Vec<usize> {
-        if let Some(mut ret) = get_line_break_utf16(input) {
+        if let Some(mut ret) = get_line_break_utf16(input, &self.segmenters) {
             ret.push(input.len());
             return ret;
         }
@@ -523,6 +1200,369 @@ impl<'a> LineBreakIterator<'a> {
     */
 }
 
+/// A unit of text between two break opportunities.
+///
+/// This is created by `LineBreakIterator::segments` and `line_break_segments`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LineSegment<'a> {
+    /// The byte range of the segment.
+    pub range: Range<usize>,
+    /// The text of the segment.
+    pub text: &'a str,
+    /// Whether the segment ends with a mandatory break.
+    pub kind: BreakKind,
+    /// The byte range of the spaces and tabs at the end of the segment, followed by the line
+    /// terminator of a mandatory break. It hangs at the end of a line in CSS.
+    pub trailing_whitespace: Range<usize>,
+}
+
+/// An iterator over the segments between break opportunities.
+pub struct LineSegments<'a> {
+    iter: WithBreakKind<LineBreakIterator<'a>>,
+    input: &'a str,
+    start: usize,
+}
+
+impl<'a> Iterator for LineSegments<'a> {
+    type Item = LineSegment<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (end, kind) = self.iter.next()?;
+        let start = self.start;
+        self.start = end;
+        let text = &self.input[start..end];
+        let mut trimmed = text;
+        if kind == BreakKind::Mandatory {
+            trimmed = trimmed.strip_suffix("\r\n").unwrap_or_else(|| {
+                let mut chars = trimmed.chars();
+                chars.next_back();
+                chars.as_str()
+            });
+        }
+        let trimmed = trimmed.trim_end_matches(&[' ', '\t'][..]);
+        Some(LineSegment {
+            range: start..end,
+            text,
+            kind,
+            trailing_whitespace: start + trimmed.len()..end,
+        })
+    }
+}
+
+impl<'a> LineBreakIterator<'a> {
+    /// Return an iterator over the segments between break opportunities.
+    ///
+    /// ```rust
+    /// use uax14_rs::{BreakKind, LineBreakIterator};
+    ///
+    /// let mut segments = LineBreakIterator::new("Hello  World\n").segments();
+    /// let segment = segments.next().unwrap();
+    /// assert_eq!(segment.text, "Hello  ");
+    /// assert_eq!(segment.kind, BreakKind::Allowed);
+    /// assert_eq!(segment.trailing_whitespace, 5..7);
+    /// let segment = segments.next().unwrap();
+    /// assert_eq!(segment.text, "World\n");
+    /// assert_eq!(segment.kind, BreakKind::Mandatory);
+    /// assert_eq!(segment.trailing_whitespace, 12..13);
+    /// assert_eq!(segments.next(), None);
+    /// ```
+    pub fn segments(self) -> LineSegments<'a> {
+        let input = self.iter.iter;
+        let start = self.front_break;
+        LineSegments {
+            iter: self.with_break_kind(),
+            input,
+            start,
+        }
+    }
+}
+
+/// Return an iterator over the segments between break opportunities of `input`.
+pub fn line_break_segments(input: &str) -> LineSegments<'_> {
+    LineBreakIterator::new(input).segments()
+}
+
+/// Decode the code point at `offset` of potentially ill-formed UTF-8, and return it and its
+/// length. Each maximal subpart of an ill-formed sequence is decoded as U+FFFD like WHATWG
+/// Encoding Standard.
+fn decode_utf8(input: &[u8], offset: usize) -> (char, usize) {
+    let lead = input[offset];
+    if lead < 0x80 {
+        return (lead as char, 1);
+    }
+    let (len, mut lower, mut upper) = match lead {
+        0xc2..=0xdf => (2, 0x80, 0xbf),
+        0xe0 => (3, 0xa0, 0xbf),
+        0xe1..=0xec | 0xee..=0xef => (3, 0x80, 0xbf),
+        0xed => (3, 0x80, 0x9f),
+        0xf0 => (4, 0x90, 0xbf),
+        0xf1..=0xf3 => (4, 0x80, 0xbf),
+        0xf4 => (4, 0x80, 0x8f),
+        _ => return (char::REPLACEMENT_CHARACTER, 1),
+    };
+    let mut ch = (lead as u32) & (0x7f >> len);
+    for i in 1..len {
+        match input.get(offset + i) {
+            Some(&b) if b >= lower && b <= upper => {
+                ch = (ch << 6) | (b as u32 & 0x3f);
+                lower = 0x80;
+                upper = 0xbf;
+            }
+            _ => return (char::REPLACEMENT_CHARACTER, i),
+        }
+    }
+    (char::from_u32(ch).unwrap(), len)
+}
+
+/// Potentially ill-formed UTF-8 version of line break iterator.
+#[derive(Clone)]
+struct PotentiallyIllFormedUTF8Indices<'a> {
+    front_offset: usize,
+    iter: &'a [u8],
+}
+
+impl<'a> Iterator for PotentiallyIllFormedUTF8Indices<'a> {
+    type Item = (usize, char);
+
+    #[inline]
+    fn next(&mut self) -> Option<(usize, char)> {
+        if self.front_offset >= self.iter.len() {
+            return None;
+        }
+        let (ch, len) = decode_utf8(self.iter, self.front_offset);
+        let index = self.front_offset;
+        self.front_offset += len;
+        Some((index, ch))
+    }
+}
+
+impl<'a> PotentiallyIllFormedUTF8Indices<'a> {
+    /// Return the code point that ends at `offset` and its index.
+    fn char_before(&self, offset: usize) -> Option<(usize, char)> {
+        if offset == 0 {
+            return None;
+        }
+        // A sequence of 2 bytes or more starts with a lead byte that can't be a trail byte of
+        // another sequence.
+        for len in 2..=core::cmp::min(offset, 4) {
+            let (ch, decoded_len) = decode_utf8(self.iter, offset - len);
+            if decoded_len == len {
+                return Some((offset - len, ch));
+            }
+        }
+        Some((offset - 1, decode_utf8(self.iter, offset - 1).0))
+    }
+}
+
+break_iterator_impl!(
+    LineBreakIteratorPotentiallyIllFormedUTF8,
+    PotentiallyIllFormedUTF8Indices<'a>,
+    char
+);
+
+impl<'a> LineBreakIteratorPotentiallyIllFormedUTF8<'a> {
+    /// Create line break iterator using potentially ill-formed UTF-8 string. Ill-formed
+    /// sequences are treated as U+FFFD, and the offsets are the ones of `input`.
+    ///
+    /// ```rust
+    /// use uax14_rs::LineBreakIteratorPotentiallyIllFormedUTF8;
+    ///
+    /// let breaks: Vec<usize> =
+    ///     LineBreakIteratorPotentiallyIllFormedUTF8::new(b"Hello \xF0\x90\x80World").collect();
+    /// assert_eq!(breaks, [6, 14]);
+    /// ```
+    pub fn new(input: &[u8]) -> LineBreakIteratorPotentiallyIllFormedUTF8 {
+        LineBreakIteratorPotentiallyIllFormedUTF8::new_with_options(
+            input,
+            &LineBreakOptions::default(),
+        )
+    }
+
+    /// Create line break iterator with CSS rules using potentially ill-formed UTF-8 string.
+    pub fn new_with_break_rule(
+        input: &[u8],
+        line_break_rule: LineBreakRule,
+        word_break_rule: WordBreakRule,
+        ja_zh: bool,
+    ) -> LineBreakIteratorPotentiallyIllFormedUTF8 {
+        let options = LineBreakOptions::from_break_rules(BreakRules {
+            line_break_rule,
+            word_break_rule,
+            ja_zh,
+        });
+        LineBreakIteratorPotentiallyIllFormedUTF8::new_with_options(input, &options)
+    }
+
+    /// Create line break iterator with options using potentially ill-formed UTF-8 string.
+    pub fn new_with_options(
+        input: &'a [u8],
+        options: &LineBreakOptions,
+    ) -> LineBreakIteratorPotentiallyIllFormedUTF8<'a> {
+        LineBreakIteratorPotentiallyIllFormedUTF8::from_indices(
+            PotentiallyIllFormedUTF8Indices {
+                front_offset: 0,
+                iter: input,
+            },
+            input.len(),
+            options,
+        )
+    }
+
+    fn get_linebreak_property(&mut self) -> u8 {
+        self.get_linebreak_property_with_rule(self.current_pos_data.unwrap().1)
+    }
+
+    fn get_linebreak_property_with_rule(&self, c: char) -> u8 {
+        get_linebreak_property_with_rule(c, self.break_rule, self.word_break_rule, self.ja_zh)
+    }
+
+    fn is_break_by_normal(&mut self) -> bool {
+        is_break_utf32_by_normal(self.current_pos_data.unwrap().1 as u32, self.ja_zh)
+    }
+
+    #[inline]
+    fn use_complex_breaking(c: char) -> bool {
+        use_complex_breaking_utf32(c as u32)
+    }
+
+    fn get_line_break_by_platform_fallback(&mut self, input: &[u16]) -> Vec<usize> {
+        if let Some(mut ret) = get_line_break_utf16(input, &self.segmenters) {
+            ret.push(input.len());
+            return ret;
+        }
+        [input.len()].to_vec()
+    }
+}
+
+/// Line break iterator over text that is split into pieces, such as the chunks of a rope.
+#[derive(Clone)]
+struct PiecesIndices<'a> {
+    front_offset: usize,
+    iter: &'a [&'a str],
+    // The piece that contains `front_offset` and its start offset.
+    piece: usize,
+    piece_start: usize,
+}
+
+impl<'a> PiecesIndices<'a> {
+    /// Return the piece that contains `offset` and its start offset.
+    fn find_piece(&self, offset: usize) -> (usize, usize) {
+        let mut piece = self.piece;
+        let mut start = self.piece_start;
+        while offset < start {
+            piece -= 1;
+            start -= self.iter[piece].len();
+        }
+        while piece < self.iter.len() && offset >= start + self.iter[piece].len() {
+            start += self.iter[piece].len();
+            piece += 1;
+        }
+        (piece, start)
+    }
+
+    /// Return the code point that ends at `offset` and its index.
+    fn char_before(&self, offset: usize) -> Option<(usize, char)> {
+        if offset == 0 {
+            return None;
+        }
+        let (piece, start) = self.find_piece(offset - 1);
+        let ch = self.iter[piece][..offset - start].chars().next_back()?;
+        Some((offset - ch.len_utf8(), ch))
+    }
+}
+
+impl<'a> Iterator for PiecesIndices<'a> {
+    type Item = (usize, char);
+
+    #[inline]
+    fn next(&mut self) -> Option<(usize, char)> {
+        let (piece, start) = self.find_piece(self.front_offset);
+        self.piece = piece;
+        self.piece_start = start;
+        let ch = self.iter.get(piece)?[self.front_offset - start..]
+            .chars()
+            .next()?;
+        let index = self.front_offset;
+        self.front_offset += ch.len_utf8();
+        Some((index, ch))
+    }
+}
+
+break_iterator_impl!(LineBreakIteratorPieces, PiecesIndices<'a>, char);
+
+impl<'a> LineBreakIteratorPieces<'a> {
+    /// Create line break iterator over text split into pieces. The offsets are the same as
+    /// the ones of `LineBreakIterator` over the concatenated text.
+    ///
+    /// ```rust
+    /// use uax14_rs::LineBreakIteratorPieces;
+    ///
+    /// let pieces = ["Hello Wo", "rld, ", "", "hello"];
+    /// let breaks: Vec<usize> = LineBreakIteratorPieces::new(&pieces).collect();
+    /// assert_eq!(breaks, [6, 13, 18]);
+    /// ```
+    pub fn new(input: &'a [&'a str]) -> LineBreakIteratorPieces<'a> {
+        LineBreakIteratorPieces::new_with_options(input, &LineBreakOptions::default())
+    }
+
+    /// Create line break iterator with CSS rules over text split into pieces.
+    pub fn new_with_break_rule(
+        input: &'a [&'a str],
+        line_break_rule: LineBreakRule,
+        word_break_rule: WordBreakRule,
+        ja_zh: bool,
+    ) -> LineBreakIteratorPieces<'a> {
+        let options = LineBreakOptions::from_break_rules(BreakRules {
+            line_break_rule,
+            word_break_rule,
+            ja_zh,
+        });
+        LineBreakIteratorPieces::new_with_options(input, &options)
+    }
+
+    /// Create line break iterator with options over text split into pieces.
+    pub fn new_with_options(
+        input: &'a [&'a str],
+        options: &LineBreakOptions,
+    ) -> LineBreakIteratorPieces<'a> {
+        LineBreakIteratorPieces::from_indices(
+            PiecesIndices {
+                front_offset: 0,
+                iter: input,
+                piece: 0,
+                piece_start: 0,
+            },
+            input.iter().map(|piece| piece.len()).sum(),
+            options,
+        )
+    }
+
+    fn get_linebreak_property(&mut self) -> u8 {
+        self.get_linebreak_property_with_rule(self.current_pos_data.unwrap().1)
+    }
+
+    fn get_linebreak_property_with_rule(&self, c: char) -> u8 {
+        get_linebreak_property_with_rule(c, self.break_rule, self.word_break_rule, self.ja_zh)
+    }
+
+    fn is_break_by_normal(&mut self) -> bool {
+        is_break_utf32_by_normal(self.current_pos_data.unwrap().1 as u32, self.ja_zh)
+    }
+
+    #[inline]
+    fn use_complex_breaking(c: char) -> bool {
+        use_complex_breaking_utf32(c as u32)
+    }
+
+    fn get_line_break_by_platform_fallback(&mut self, input: &[u16]) -> Vec<usize> {
+        if let Some(mut ret) = get_line_break_utf16(input, &self.segmenters) {
+            ret.push(input.len());
+            return ret;
+        }
+        [input.len()].to_vec()
+    }
+}
+
 /// Latin-1 version of line break iterator.
 #[derive(Clone)]
 struct Latin1Indices<'a> {
@@ -545,23 +1585,22 @@ impl<'a> Iterator for Latin1Indices<'a> {
     }
 }
 
+impl<'a> Latin1Indices<'a> {
+    /// Return the code point that ends at `offset` and its index.
+    fn char_before(&self, offset: usize) -> Option<(usize, u8)> {
+        if offset == 0 {
+            return None;
+        }
+        Some((offset - 1, self.iter[offset - 1]))
+    }
+}
+
 break_iterator_impl!(LineBreakIteratorLatin1, Latin1Indices<'a>, u8);
 
 impl<'a> LineBreakIteratorLatin1<'a> {
     /// Create line break iterator using Latin-1/8-bit string.
     pub fn new(input: &[u8]) -> LineBreakIteratorLatin1 {
-        LineBreakIteratorLatin1 {
-            iter: Latin1Indices {
-                front_offset: 0,
-                iter: input,
-            },
-            len: input.len(),
-            current_pos_data: None,
-            result_cache: Vec::new(),
-            break_rule: LineBreakRule::Strict,
-            word_break_rule: WordBreakRule::Normal,
-            ja_zh: false,
-        }
+        LineBreakIteratorLatin1::new_with_options(input, &LineBreakOptions::default())
     }
 
     /// Create line break iterator with CSS rules using Latin-1/8-bit string.
@@ -571,18 +1610,27 @@ impl<'a> LineBreakIteratorLatin1<'a> {
         word_break_rule: WordBreakRule,
         ja_zh: bool,
     ) -> LineBreakIteratorLatin1 {
-        LineBreakIteratorLatin1 {
-            iter: Latin1Indices {
+        let options = LineBreakOptions::from_break_rules(BreakRules {
+            line_break_rule,
+            word_break_rule,
+            ja_zh,
+        });
+        LineBreakIteratorLatin1::new_with_options(input, &options)
+    }
+
+    /// Create line break iterator with options using Latin-1/8-bit string.
+    pub fn new_with_options(
+        input: &'a [u8],
+        options: &LineBreakOptions,
+    ) -> LineBreakIteratorLatin1<'a> {
+        LineBreakIteratorLatin1::from_indices(
+            Latin1Indices {
                 front_offset: 0,
                 iter: input,
             },
-            len: input.len(),
-            current_pos_data: None,
-            result_cache: Vec::new(),
-            break_rule: line_break_rule,
-            word_break_rule,
-            ja_zh,
-        }
+            input.len(),
+            options,
+        )
     }
 
     fn get_linebreak_property(&mut self) -> u8 {
@@ -590,9 +1638,14 @@ impl<'a> LineBreakIteratorLatin1<'a> {
         self.get_linebreak_property_with_rule(self.current_pos_data.unwrap().1)
     }
 
-    fn get_linebreak_property_with_rule(&mut self, c: u8) -> u8 {
-        // No CJ on Latin1
-        get_linebreak_property_latin1(c)
+    fn get_linebreak_property_with_rule(&self, c: u8) -> u8 {
+        // Latin-1 is the same as the first 256 code points of Unicode.
+        get_linebreak_property_utf32_with_rule(
+            c as u32,
+            self.break_rule,
+            self.word_break_rule,
+            self.ja_zh,
+        )
     }
 
     fn is_break_by_normal(&mut self) -> bool {
@@ -604,8 +1657,13 @@ impl<'a> LineBreakIteratorLatin1<'a> {
         false
     }
 
-    fn get_line_break_by_platform_fallback(&mut self, _input: &[u16]) -> Vec<usize> {
-        panic!("not reachable");
+    // Latin-1 text has no complex language text, but this is the same as the others.
+    fn get_line_break_by_platform_fallback(&mut self, input: &[u16]) -> Vec<usize> {
+        if let Some(mut ret) = get_line_break_utf16(input, &self.segmenters) {
+            ret.push(input.len());
+            return ret;
+        }
+        [input.len()].to_vec()
     }
 }
 
@@ -628,20 +1686,41 @@ impl<'a> Iterator for UTF16Indices<'a> {
         let index = self.front_offset;
         self.front_offset += 1;
 
-        if (ch & 0xfc00) != 0xd800 {
+        if (ch & 0xf800) != 0xd800 {
             return Some((index, ch as u32));
         }
 
-        let mut ch = ch as u32;
-        if self.front_offset < self.iter.len() {
+        let ch = ch as u32;
+        if (ch & 0xfc00) == 0xd800 && self.front_offset < self.iter.len() {
             let next = self.iter[self.front_offset] as u32;
             if (next & 0xfc00) == 0xdc00 {
-                ch = ((ch & 0x3ff) << 10) + (next & 0x3ff) + 0x10000;
                 self.front_offset += 1;
-                return Some((index, ch));
+                return Some((index, ((ch & 0x3ff) << 10) + (next & 0x3ff) + 0x10000));
             }
         }
-        Some((index, ch))
+        // Lone surrogate
+        Some((index, char::REPLACEMENT_CHARACTER as u32))
+    }
+}
+
+impl<'a> UTF16Indices<'a> {
+    /// Return the code point that ends at `offset` and its index.
+    fn char_before(&self, offset: usize) -> Option<(usize, u32)> {
+        if offset == 0 {
+            return None;
+        }
+        let ch = self.iter[offset - 1] as u32;
+        if (ch & 0xf800) != 0xd800 {
+            return Some((offset - 1, ch));
+        }
+        if (ch & 0xfc00) == 0xdc00 && offset >= 2 {
+            let prev = self.iter[offset - 2] as u32;
+            if (prev & 0xfc00) == 0xd800 {
+                return Some((offset - 2, ((prev & 0x3ff) << 10) + (ch & 0x3ff) + 0x10000));
+            }
+        }
+        // Lone surrogate
+        Some((offset - 1, char::REPLACEMENT_CHARACTER as u32))
     }
 }
 
@@ -650,18 +1729,7 @@ break_iterator_impl!(LineBreakIteratorUTF16, UTF16Indices<'a>, u32);
 impl<'a> LineBreakIteratorUTF16<'a> {
     /// Create line break iterator using UTF-16 string.
     pub fn new(input: &[u16]) -> LineBreakIteratorUTF16 {
-        LineBreakIteratorUTF16 {
-            iter: UTF16Indices {
-                front_offset: 0,
-                iter: input,
-            },
-            len: input.len(),
-            current_pos_data: None,
-            result_cache: Vec::new(),
-            break_rule: LineBreakRule::Strict,
-            word_break_rule: WordBreakRule::Normal,
-            ja_zh: false,
-        }
+        LineBreakIteratorUTF16::new_with_options(input, &LineBreakOptions::default())
     }
 
     /// Create line break iterator with CSS rules using UTF-16 string.
@@ -671,30 +1739,183 @@ impl<'a> LineBreakIteratorUTF16<'a> {
         word_break_rule: WordBreakRule,
         ja_zh: bool,
     ) -> LineBreakIteratorUTF16 {
-        LineBreakIteratorUTF16 {
-            iter: UTF16Indices {
+        let options = LineBreakOptions::from_break_rules(BreakRules {
+            line_break_rule,
+            word_break_rule,
+            ja_zh,
+        });
+        LineBreakIteratorUTF16::new_with_options(input, &options)
+    }
+
+    /// Create line break iterator with options using UTF-16 string.
+    pub fn new_with_options(
+        input: &'a [u16],
+        options: &LineBreakOptions,
+    ) -> LineBreakIteratorUTF16<'a> {
+        LineBreakIteratorUTF16::from_indices(
+            UTF16Indices {
                 front_offset: 0,
                 iter: input,
             },
-            len: input.len(),
-            current_pos_data: None,
-            result_cache: Vec::new(),
-            break_rule: line_break_rule,
+            input.len(),
+            options,
+        )
+    }
+
+    fn get_linebreak_property(&mut self) -> u8 {
+        self.get_linebreak_property_with_rule(self.current_pos_data.unwrap().1)
+    }
+
+    fn get_linebreak_property_with_rule(&self, c: u32) -> u8 {
+        get_linebreak_property_utf32_with_rule(c, self.break_rule, self.word_break_rule, self.ja_zh)
+    }
+
+    fn is_break_by_normal(&mut self) -> bool {
+        is_break_utf32_by_normal(self.current_pos_data.unwrap().1 as u32, self.ja_zh)
+    }
+
+    #[inline]
+    fn use_complex_breaking(c: u32) -> bool {
+        use_complex_breaking_utf32(c)
+    }
+
+    fn get_line_break_by_platform_fallback(&mut self, input: &[u16]) -> Vec<usize> {
+        if let Some(mut ret) = get_line_break_utf16(input, &self.segmenters) {
+            ret.push(input.len());
+            return ret;
+        }
+        [input.len()].to_vec()
+    }
+}
+
+#[derive(Clone, Copy)]
+enum UTF32Slice<'a> {
+    U32(&'a [u32]),
+    Char(&'a [char]),
+}
+
+/// UTF-32 version of line break iterator.
+#[derive(Clone)]
+struct UTF32Indices<'a> {
+    front_offset: usize,
+    iter: UTF32Slice<'a>,
+}
+
+impl<'a> UTF32Indices<'a> {
+    fn len(&self) -> usize {
+        match self.iter {
+            UTF32Slice::U32(s) => s.len(),
+            UTF32Slice::Char(s) => s.len(),
+        }
+    }
+
+    /// Return the code point at `offset`. Surrogates and values out of Unicode range are
+    /// replaced with U+FFFD.
+    fn get(&self, offset: usize) -> u32 {
+        match self.iter {
+            UTF32Slice::U32(s) => match char::from_u32(s[offset]) {
+                Some(c) => c as u32,
+                None => char::REPLACEMENT_CHARACTER as u32,
+            },
+            UTF32Slice::Char(s) => s[offset] as u32,
+        }
+    }
+
+    /// Return the code point that ends at `offset` and its index.
+    fn char_before(&self, offset: usize) -> Option<(usize, u32)> {
+        if offset == 0 {
+            return None;
+        }
+        Some((offset - 1, self.get(offset - 1)))
+    }
+}
+
+impl<'a> Iterator for UTF32Indices<'a> {
+    type Item = (usize, u32);
+
+    #[inline]
+    fn next(&mut self) -> Option<(usize, u32)> {
+        if self.front_offset >= self.len() {
+            return None;
+        }
+        let index = self.front_offset;
+        self.front_offset += 1;
+        Some((index, self.get(index)))
+    }
+}
+
+break_iterator_impl!(LineBreakIteratorUTF32, UTF32Indices<'a>, u32);
+
+impl<'a> LineBreakIteratorUTF32<'a> {
+    /// Create line break iterator using UTF-32 string. The offsets are code point indices.
+    pub fn new(input: &[u32]) -> LineBreakIteratorUTF32 {
+        LineBreakIteratorUTF32::new_with_options(input, &LineBreakOptions::default())
+    }
+
+    /// Create line break iterator with CSS rules using UTF-32 string.
+    pub fn new_with_break_rule(
+        input: &[u32],
+        line_break_rule: LineBreakRule,
+        word_break_rule: WordBreakRule,
+        ja_zh: bool,
+    ) -> LineBreakIteratorUTF32 {
+        let options = LineBreakOptions::from_break_rules(BreakRules {
+            line_break_rule,
             word_break_rule,
             ja_zh,
-        }
+        });
+        LineBreakIteratorUTF32::new_with_options(input, &options)
+    }
+
+    /// Create line break iterator with options using UTF-32 string.
+    pub fn new_with_options(
+        input: &'a [u32],
+        options: &LineBreakOptions,
+    ) -> LineBreakIteratorUTF32<'a> {
+        LineBreakIteratorUTF32::from_indices(
+            UTF32Indices {
+                front_offset: 0,
+                iter: UTF32Slice::U32(input),
+            },
+            input.len(),
+            options,
+        )
+    }
+
+    /// Create line break iterator with options using a slice of `char`.
+    ///
+    /// ```rust
+    /// use uax14_rs::{LineBreakIteratorUTF32, LineBreakOptions};
+    ///
+    /// let text: Vec<char> = "Hello World".chars().collect();
+    /// let breaks: Vec<usize> =
+    ///     LineBreakIteratorUTF32::new_with_chars(&text, &LineBreakOptions::default()).collect();
+    /// assert_eq!(breaks, [6, 11]);
+    /// ```
+    pub fn new_with_chars(
+        input: &'a [char],
+        options: &LineBreakOptions,
+    ) -> LineBreakIteratorUTF32<'a> {
+        LineBreakIteratorUTF32::from_indices(
+            UTF32Indices {
+                front_offset: 0,
+                iter: UTF32Slice::Char(input),
+            },
+            input.len(),
+            options,
+        )
     }
 
     fn get_linebreak_property(&mut self) -> u8 {
         self.get_linebreak_property_with_rule(self.current_pos_data.unwrap().1)
     }
 
-    fn get_linebreak_property_with_rule(&mut self, c: u32) -> u8 {
+    fn get_linebreak_property_with_rule(&self, c: u32) -> u8 {
         get_linebreak_property_utf32_with_rule(c, self.break_rule, self.word_break_rule, self.ja_zh)
     }
 
     fn is_break_by_normal(&mut self) -> bool {
-        is_break_utf32_by_normal(self.current_pos_data.unwrap().1 as u32, self.ja_zh)
+        is_break_utf32_by_normal(self.current_pos_data.unwrap().1, self.ja_zh)
     }
 
     #[inline]
@@ -703,7 +1924,7 @@ impl<'a> LineBreakIteratorUTF16<'a> {
     }
 
     fn get_line_break_by_platform_fallback(&mut self, input: &[u16]) -> Vec<usize> {
-        if let Some(mut ret) = get_line_break_utf16(input) {
+        if let Some(mut ret) = get_line_break_utf16(input, &self.segmenters) {
             ret.push(input.len());
             return ret;
         }
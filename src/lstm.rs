@@ -1,5 +1,5 @@
 use icu_segmenter_lstm::lstm::Lstm;
-use std::char::decode_utf16;
+use std::char::{decode_utf16, REPLACEMENT_CHARACTER};
 
 // TODO:
 // json file is big, So I should use anoher binary format like npy.
@@ -90,8 +90,9 @@ pub fn get_line_break_utf8(input: &str) -> Option<Vec<usize>> {
 }
 
 pub fn get_line_break_utf16(input: &[u16]) -> Option<Vec<usize>> {
+    // Lone surrogates are replaced with U+FFFD, so the offsets are kept.
     let s: String = decode_utf16(input.iter().cloned())
-        .map(|r| r.unwrap())
+        .map(|r| r.unwrap_or(REPLACEMENT_CHARACTER))
         .collect();
     let iter = LstmSegmenterIteratorUTF16::new(&*THAI_LSTM, &s);
     let result: Vec<usize> = iter.collect();
@@ -126,5 +127,20 @@ mod tests {
         let text: [u16; 4] = [0x0e20, 0x0e32, 0x0e29, 0x0e32];
         let breaks = get_line_break_utf16(&text);
         assert_eq!(breaks, None, "Thai test");
+
+        // Lone surrogates don't change the offsets.
+        let text: [u16; 16] = [
+            0x0e20, 0x0e32, 0x0e29, 0x0e32, 0x0e44, 0x0e17, 0x0e22, 0xd800, 0x0e20, 0x0e32, 0x0e29,
+            0x0e32, 0x0e44, 0x0e17, 0x0e22, 0xdc00,
+        ];
+        let breaks = get_line_break_utf16(&text).unwrap();
+        assert!(
+            breaks.windows(2).all(|w| w[0] < w[1]),
+            "Thai test with lone surrogates"
+        );
+        assert!(
+            breaks.iter().all(|&b| b > 0 && b < text.len()),
+            "Thai test with lone surrogates"
+        );
     }
 }
@@ -0,0 +1,106 @@
+// Burmese lines may be broken between syllables, so Myanmar text is broken by the syllable rules
+// without a model. This is syllable-level only: there is no word list to join syllables into
+// words, and `LineBreakOptions::dictionary` can give one.
+//
+// A syllable starts at a consonant or an independent vowel unless it is stacked under another
+// consonant by VIRAMA (U+1039), or it is the final consonant of the previous syllable killed by
+// ASAT (U+103A) or stacked over the next one.
+
+const VIRAMA: u16 = 0x1039;
+const ASAT: u16 = 0x103a;
+const DOT_BELOW: u16 = 0x1037;
+
+// Consonants and independent vowels of Myanmar, Myanmar Extended-A and Extended-B
+fn is_consonant(c: u16) -> bool {
+    matches!(
+        c,
+        0x1000..=0x102a
+            | 0x103f
+            | 0x1050..=0x1055
+            | 0x105a..=0x105d
+            | 0x1061
+            | 0x1065..=0x1066
+            | 0x106e..=0x1070
+            | 0x1075..=0x1081
+            | 0x108e
+            | 0xaa60..=0xaa6f
+            | 0xaa71..=0xaa76
+            | 0xaa7a
+            | 0xa9e0..=0xa9e4
+            | 0xa9e7..=0xa9ef
+            | 0xa9fa..=0xa9fe
+    )
+}
+
+// Vowels, medials, tone marks and signs that belong to the previous consonant
+fn is_mark(c: u16) -> bool {
+    matches!(
+        c,
+        0x102b..=0x103e
+            | 0x1056..=0x1059
+            | 0x105e..=0x1060
+            | 0x1062..=0x1064
+            | 0x1067..=0x106d
+            | 0x1071..=0x1074
+            | 0x1082..=0x108d
+            | 0x108f
+            | 0x109a..=0x109d
+            | 0xa9e5
+            | 0xaa7b..=0xaa7d
+    )
+}
+
+// MEDIAL YA (U+103B) and MEDIAL HA (U+103E) may be between a final consonant and ASAT.
+fn is_syllable_start(input: &[u16], i: usize) -> bool {
+    if !is_consonant(input[i]) || input[i - 1] == VIRAMA {
+        return false;
+    }
+    let mut next = input[i + 1..].iter();
+    let mut c = next.next();
+    if c == Some(&0x103b) || c == Some(&0x103e) {
+        c = next.next();
+    }
+    !matches!(c, Some(&DOT_BELOW) | Some(&VIRAMA) | Some(&ASAT))
+}
+
+// A final consonant is killed by ASAT or stacked by VIRAMA, so it isn't a boundary either.
+pub fn is_cluster_boundary(input: &[u16], offset: usize) -> bool {
+    let before_final = matches!(input.get(offset + 1), Some(&VIRAMA) | Some(&ASAT));
+    !(input[offset - 1] == VIRAMA || is_mark(input[offset]) || before_final)
+}
+
+pub fn get_line_break_utf16(input: &[u16]) -> Option<Vec<usize>> {
+    let result: Vec<usize> = (1..input.len())
+        .filter(|&i| is_syllable_start(input, i))
+        .collect();
+    if result.is_empty() {
+        return None;
+    }
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::complex::get_segments;
+    use crate::myanmar::get_line_break_utf16;
+
+    fn get_syllables(s: &str) -> Vec<String> {
+        get_segments(s, get_line_break_utf16)
+    }
+
+    #[test]
+    fn myanmar_syllable_break() {
+        assert_eq!(get_syllables("မြန်မာစာ"), ["မြန်", "မာ", "စာ"]);
+        assert_eq!(
+            get_syllables("ကျေးဇူးတင်ပါတယ်"),
+            ["ကျေး", "ဇူး", "တင်", "ပါ", "တယ်"]
+        );
+        // Stacked consonants and kinzi aren't broken.
+        assert_eq!(get_syllables("ကမ္ဘာ"), ["ကမ္ဘာ"]);
+        assert_eq!(get_syllables("မင်္ဂလာပါ"), ["မင်္ဂ", "လာ", "ပါ"]);
+        assert_eq!(get_syllables("ဗုဒ္ဓ"), ["ဗုဒ္ဓ"]);
+        // Medials and DOT BELOW stay in the syllable.
+        assert_eq!(get_syllables("လျှော့ပေါ့"), ["လျှော့", "ပေါ့"]);
+        assert_eq!(get_syllables("က"), ["က"]);
+    }
+}
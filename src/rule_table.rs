@@ -3308,3 +3308,347 @@ pub const UAX14_RULE_TABLE: [i8; 3249] = [
     -128,
     -128,
 ];
+
+pub const UAX14_RULE_ID_TABLE: [[u8; 57]; 57] = [
+    // AI
+    [
+        29, 29, 34, 19, 34, 3, 18, 19, 11, 7, 11, 3, 34, 34, 11, 10, 34, 34, 29, 19, 34, 22, 11,
+        34, 34, 34, 3, 3, 19, 23, 34, 31, 25, 25, 17, 34, 29, 34, 4, 11, 8, 29, 4, 7, 34, 34, 34,
+        34, 34, 34, 34, 34, 34, 34, 34, 34, 0,
+    ],
+    // AL
+    [
+        29, 29, 34, 19, 34, 3, 18, 19, 11, 7, 11, 3, 34, 34, 11, 10, 34, 34, 29, 19, 34, 22, 11,
+        34, 34, 34, 3, 3, 19, 23, 34, 31, 25, 25, 17, 34, 29, 34, 4, 11, 8, 29, 4, 7, 34, 34, 34,
+        34, 34, 34, 34, 34, 34, 34, 34, 34, 0,
+    ],
+    // B2
+    [
+        34, 34, 15, 19, 34, 3, 18, 19, 11, 7, 11, 3, 34, 34, 11, 10, 34, 34, 34, 19, 34, 22, 11,
+        34, 34, 34, 3, 3, 19, 34, 34, 34, 34, 34, 17, 34, 34, 34, 15, 11, 8, 34, 4, 7, 34, 34, 34,
+        34, 34, 34, 34, 34, 34, 34, 34, 34, 0,
+    ],
+    // BA
+    [
+        34, 34, 34, 19, 34, 3, 18, 19, 11, 7, 11, 3, 34, 34, 11, 10, 34, 34, 34, 19, 34, 22, 11,
+        34, 34, 34, 3, 3, 19, 34, 34, 34, 34, 34, 17, 34, 34, 34, 4, 11, 8, 34, 4, 7, 34, 34, 34,
+        34, 34, 34, 34, 34, 34, 34, 34, 34, 0,
+    ],
+    // BB
+    [
+        19, 19, 19, 19, 19, 3, 18, 19, 11, 7, 11, 3, 19, 19, 11, 10, 19, 19, 19, 19, 19, 19, 11,
+        19, 19, 19, 3, 3, 19, 19, 19, 19, 19, 19, 17, 19, 19, 19, 4, 11, 8, 19, 4, 7, 19, 19, 19,
+        19, 19, 19, 19, 19, 19, 19, 19, 19, 0,
+    ],
+    // BK
+    [
+        1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+        1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0,
+    ],
+    // CB
+    [
+        18, 18, 18, 18, 18, 3, 18, 18, 11, 7, 11, 3, 18, 18, 11, 10, 18, 18, 18, 18, 18, 18, 11,
+        18, 18, 18, 3, 3, 18, 18, 18, 18, 18, 18, 17, 18, 18, 18, 4, 11, 8, 18, 4, 7, 18, 18, 18,
+        18, 18, 18, 18, 18, 18, 18, 18, 18, 0,
+    ],
+    // CJ
+    [
+        34, 34, 34, 19, 34, 3, 18, 19, 11, 7, 11, 3, 34, 34, 11, 10, 34, 34, 34, 19, 34, 22, 11,
+        34, 34, 34, 3, 3, 19, 34, 34, 34, 34, 34, 17, 34, 34, 34, 4, 11, 8, 34, 4, 7, 34, 34, 34,
+        34, 34, 34, 34, 34, 34, 34, 34, 34, 0,
+    ],
+    // CL
+    [
+        34, 34, 34, 19, 34, 3, 18, 14, 11, 7, 11, 3, 34, 34, 11, 10, 34, 34, 34, 19, 34, 22, 11,
+        34, 34, 34, 3, 3, 14, 34, 34, 34, 34, 34, 17, 34, 34, 34, 14, 11, 8, 34, 4, 7, 34, 34, 34,
+        34, 34, 34, 34, 34, 34, 34, 34, 34, 0,
+    ],
+    // CM
+    [
+        29, 29, 34, 19, 34, 3, 18, 19, 11, 7, 11, 3, 34, 34, 11, 10, 34, 34, 29, 19, 34, 22, 11,
+        34, 34, 34, 3, 3, 19, 23, 34, 31, 25, 25, 17, 34, 29, 34, 4, 11, 8, 29, 4, 7, 34, 34, 34,
+        34, 34, 34, 34, 34, 34, 34, 34, 34, 0,
+    ],
+    // CP
+    [
+        31, 31, 34, 19, 34, 3, 18, 14, 11, 7, 11, 3, 34, 34, 11, 10, 34, 34, 31, 19, 34, 22, 11,
+        34, 34, 34, 3, 3, 14, 31, 34, 34, 34, 34, 17, 34, 31, 34, 14, 11, 8, 31, 4, 7, 34, 34, 34,
+        34, 34, 34, 34, 34, 34, 34, 34, 34, 0,
+    ],
+    // CR
+    [
+        2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2,
+        2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 0,
+    ],
+    // EB
+    [
+        34, 34, 34, 19, 34, 3, 18, 19, 11, 7, 11, 3, 34, 33, 11, 10, 34, 34, 34, 19, 34, 22, 11,
+        34, 34, 34, 3, 3, 19, 34, 34, 34, 24, 34, 17, 34, 34, 34, 4, 11, 8, 34, 4, 7, 34, 34, 34,
+        34, 34, 34, 34, 34, 34, 34, 34, 34, 0,
+    ],
+    // EM
+    [
+        34, 34, 34, 19, 34, 3, 18, 19, 11, 7, 11, 3, 34, 34, 11, 10, 34, 34, 34, 19, 34, 22, 11,
+        34, 34, 34, 3, 3, 19, 34, 34, 34, 24, 34, 17, 34, 34, 34, 4, 11, 8, 34, 4, 7, 34, 34, 34,
+        34, 34, 34, 34, 34, 34, 34, 34, 34, 0,
+    ],
+    // EX
+    [
+        34, 34, 34, 19, 34, 3, 18, 19, 11, 7, 11, 3, 34, 34, 11, 10, 34, 34, 34, 19, 34, 22, 11,
+        34, 34, 34, 3, 3, 19, 34, 34, 34, 34, 34, 17, 34, 34, 34, 4, 11, 8, 34, 4, 7, 34, 34, 34,
+        34, 34, 34, 34, 34, 34, 34, 34, 34, 0,
+    ],
+    // GL
+    [
+        9, 9, 9, 9, 9, 3, 9, 9, 9, 7, 9, 3, 9, 9, 9, 9, 9, 9, 9, 9, 9, 9, 9, 9, 9, 9, 3, 3, 9, 9,
+        9, 9, 9, 9, 9, 9, 9, 9, 4, 9, 8, 9, 4, 7, 9, 9, 9, 9, 9, 9, 9, 9, 9, 9, 9, 9, 0,
+    ],
+    // H2
+    [
+        34, 34, 34, 19, 34, 3, 18, 19, 11, 7, 11, 3, 34, 34, 11, 10, 34, 34, 34, 19, 34, 22, 11,
+        34, 27, 27, 3, 3, 19, 34, 34, 34, 28, 34, 17, 34, 34, 34, 4, 11, 8, 34, 4, 7, 34, 34, 34,
+        34, 34, 34, 34, 34, 34, 34, 34, 34, 0,
+    ],
+    // H3
+    [
+        34, 34, 34, 19, 34, 3, 18, 19, 11, 7, 11, 3, 34, 34, 11, 10, 34, 34, 34, 19, 34, 22, 11,
+        34, 27, 34, 3, 3, 19, 34, 34, 34, 28, 34, 17, 34, 34, 34, 4, 11, 8, 34, 4, 7, 34, 34, 34,
+        34, 34, 34, 34, 34, 34, 34, 34, 34, 0,
+    ],
+    // HL
+    [
+        29, 29, 34, 20, 34, 3, 18, 19, 11, 7, 11, 3, 34, 34, 11, 10, 34, 34, 29, 20, 34, 22, 11,
+        34, 34, 34, 3, 3, 19, 23, 34, 31, 25, 25, 17, 34, 29, 34, 4, 11, 8, 29, 4, 7, 34, 34, 34,
+        34, 34, 34, 34, 34, 34, 34, 34, 34, 0,
+    ],
+    // HY
+    [
+        34, 34, 34, 19, 34, 3, 18, 19, 11, 7, 11, 3, 34, 34, 11, 10, 34, 34, 34, 19, 34, 22, 11,
+        34, 34, 34, 3, 3, 19, 26, 34, 34, 34, 34, 17, 34, 34, 34, 4, 11, 8, 34, 4, 7, 34, 34, 34,
+        34, 34, 34, 34, 34, 34, 34, 34, 34, 0,
+    ],
+    // ID
+    [
+        34, 34, 34, 19, 34, 3, 18, 19, 11, 7, 11, 3, 34, 34, 11, 10, 34, 34, 34, 19, 34, 22, 11,
+        34, 34, 34, 3, 3, 19, 34, 34, 34, 24, 34, 17, 34, 34, 34, 4, 11, 8, 34, 4, 7, 34, 34, 34,
+        34, 34, 34, 34, 34, 34, 34, 34, 34, 0,
+    ],
+    // IN
+    [
+        34, 34, 34, 19, 34, 3, 18, 19, 11, 7, 11, 3, 34, 34, 11, 10, 34, 34, 34, 19, 34, 22, 11,
+        34, 34, 34, 3, 3, 19, 34, 34, 34, 34, 34, 17, 34, 34, 34, 4, 11, 8, 34, 4, 7, 34, 34, 34,
+        34, 34, 34, 34, 34, 34, 34, 34, 34, 0,
+    ],
+    // IS
+    [
+        30, 30, 34, 19, 34, 3, 18, 19, 11, 7, 11, 3, 34, 34, 11, 10, 34, 34, 30, 19, 34, 22, 11,
+        34, 34, 34, 3, 3, 19, 34, 34, 34, 34, 34, 17, 34, 30, 34, 4, 11, 8, 30, 4, 7, 34, 34, 34,
+        34, 34, 34, 34, 34, 34, 34, 34, 34, 0,
+    ],
+    // JL
+    [
+        34, 34, 34, 19, 34, 3, 18, 19, 11, 7, 11, 3, 34, 34, 11, 10, 27, 27, 34, 19, 34, 22, 11,
+        27, 34, 27, 3, 3, 19, 34, 34, 34, 28, 34, 17, 34, 34, 34, 4, 11, 8, 34, 4, 7, 34, 34, 34,
+        34, 34, 34, 34, 34, 34, 34, 34, 34, 0,
+    ],
+    // JT
+    [
+        34, 34, 34, 19, 34, 3, 18, 19, 11, 7, 11, 3, 34, 34, 11, 10, 34, 34, 34, 19, 34, 22, 11,
+        34, 27, 34, 3, 3, 19, 34, 34, 34, 28, 34, 17, 34, 34, 34, 4, 11, 8, 34, 4, 7, 34, 34, 34,
+        34, 34, 34, 34, 34, 34, 34, 34, 34, 0,
+    ],
+    // JV
+    [
+        34, 34, 34, 19, 34, 3, 18, 19, 11, 7, 11, 3, 34, 34, 11, 10, 34, 34, 34, 19, 34, 22, 11,
+        34, 27, 27, 3, 3, 19, 34, 34, 34, 28, 34, 17, 34, 34, 34, 4, 11, 8, 34, 4, 7, 34, 34, 34,
+        34, 34, 34, 34, 34, 34, 34, 34, 34, 0,
+    ],
+    // LF
+    [
+        2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2,
+        2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 0,
+    ],
+    // NL
+    [
+        2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2,
+        2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 0,
+    ],
+    // NS
+    [
+        34, 34, 34, 19, 34, 3, 18, 19, 11, 7, 11, 3, 34, 34, 11, 10, 34, 34, 34, 19, 34, 22, 11,
+        34, 34, 34, 3, 3, 19, 34, 34, 34, 34, 34, 17, 34, 34, 34, 4, 11, 8, 34, 4, 7, 34, 34, 34,
+        34, 34, 34, 34, 34, 34, 34, 34, 34, 0,
+    ],
+    // NU
+    [
+        23, 23, 34, 19, 34, 3, 18, 19, 26, 7, 26, 3, 34, 34, 11, 10, 34, 34, 23, 19, 34, 22, 26,
+        34, 34, 34, 3, 3, 19, 26, 34, 31, 26, 26, 17, 34, 23, 34, 4, 26, 8, 23, 4, 7, 34, 34, 34,
+        34, 34, 34, 34, 34, 34, 34, 34, 34, 0,
+    ],
+    // OP_EA
+    [
+        12, 12, 12, 12, 12, 3, 12, 12, 11, 7, 11, 3, 12, 12, 11, 10, 12, 12, 12, 12, 12, 12, 11,
+        12, 12, 12, 3, 3, 12, 12, 12, 12, 12, 12, 12, 12, 12, 12, 12, 11, 8, 12, 4, 7, 12, 12, 12,
+        12, 12, 12, 12, 12, 12, 12, 12, 12, 0,
+    ],
+    // OP_OP30
+    [
+        12, 12, 12, 12, 12, 3, 12, 12, 11, 7, 11, 3, 12, 12, 11, 10, 12, 12, 12, 12, 12, 12, 11,
+        12, 12, 12, 3, 3, 12, 12, 12, 12, 12, 12, 12, 12, 12, 12, 12, 11, 8, 12, 4, 7, 12, 12, 12,
+        12, 12, 12, 12, 12, 12, 12, 12, 12, 0,
+    ],
+    // PO
+    [
+        25, 25, 34, 19, 34, 3, 18, 19, 11, 7, 11, 3, 34, 34, 11, 10, 34, 34, 25, 19, 34, 22, 11,
+        34, 34, 34, 3, 3, 19, 26, 26, 26, 34, 34, 17, 34, 25, 34, 4, 11, 8, 25, 4, 7, 34, 34, 34,
+        34, 34, 34, 34, 34, 34, 34, 34, 34, 0,
+    ],
+    // PR
+    [
+        25, 25, 34, 19, 34, 3, 18, 19, 11, 7, 11, 3, 24, 24, 11, 10, 28, 28, 25, 19, 24, 22, 11,
+        28, 28, 28, 3, 3, 19, 26, 26, 26, 34, 34, 17, 34, 25, 34, 4, 11, 8, 25, 4, 7, 34, 34, 34,
+        34, 34, 34, 34, 34, 34, 34, 34, 34, 0,
+    ],
+    // QU
+    [
+        17, 17, 17, 17, 17, 3, 17, 17, 11, 7, 11, 3, 17, 17, 11, 10, 17, 17, 17, 17, 17, 17, 11,
+        17, 17, 17, 3, 3, 17, 17, 13, 13, 17, 17, 17, 17, 17, 17, 13, 11, 8, 17, 4, 7, 17, 17, 17,
+        17, 17, 17, 17, 17, 17, 17, 17, 17, 0,
+    ],
+    // RI
+    [
+        34, 34, 34, 19, 34, 3, 18, 19, 11, 7, 11, 3, 34, 34, 11, 10, 34, 34, 34, 19, 34, 22, 11,
+        34, 34, 34, 3, 3, 19, 34, 34, 34, 34, 34, 17, 32, 34, 34, 4, 11, 8, 34, 4, 7, 34, 34, 34,
+        34, 34, 34, 34, 34, 34, 34, 34, 34, 0,
+    ],
+    // SA
+    [
+        29, 29, 34, 19, 34, 3, 18, 19, 11, 7, 11, 3, 34, 34, 11, 10, 34, 34, 29, 19, 34, 22, 11,
+        34, 34, 34, 3, 3, 19, 23, 34, 31, 25, 25, 17, 34, 29, 34, 4, 11, 8, 29, 4, 7, 34, 34, 34,
+        34, 34, 34, 34, 34, 34, 34, 34, 34, 0,
+    ],
+    // SG
+    [
+        34, 34, 34, 19, 34, 3, 18, 19, 11, 7, 11, 3, 34, 34, 11, 10, 34, 34, 34, 19, 34, 22, 11,
+        34, 34, 34, 3, 3, 19, 34, 34, 34, 34, 34, 17, 34, 34, 34, 4, 11, 8, 34, 4, 7, 34, 34, 34,
+        34, 34, 34, 34, 34, 34, 34, 34, 34, 0,
+    ],
+    // SP
+    [
+        16, 16, 16, 16, 16, 3, 16, 16, 11, 16, 11, 3, 16, 16, 11, 10, 16, 16, 16, 16, 16, 16, 11,
+        16, 16, 16, 3, 3, 16, 16, 16, 16, 16, 16, 16, 16, 16, 16, 4, 11, 8, 16, 4, 16, 16, 16, 16,
+        16, 16, 16, 16, 16, 16, 16, 16, 16, 0,
+    ],
+    // SY
+    [
+        34, 34, 34, 19, 34, 3, 18, 19, 11, 7, 11, 3, 34, 34, 11, 10, 34, 34, 21, 19, 34, 22, 11,
+        34, 34, 34, 3, 3, 19, 34, 34, 34, 34, 34, 17, 34, 34, 34, 4, 11, 8, 34, 4, 7, 34, 34, 34,
+        34, 34, 34, 34, 34, 34, 34, 34, 34, 0,
+    ],
+    // WJ
+    [
+        8, 8, 8, 8, 8, 3, 8, 8, 8, 7, 8, 3, 8, 8, 8, 8, 8, 8, 8, 8, 8, 8, 8, 8, 8, 8, 3, 3, 8, 8,
+        8, 8, 8, 8, 8, 8, 8, 8, 4, 8, 8, 8, 4, 7, 8, 8, 8, 8, 8, 8, 8, 8, 8, 8, 8, 8, 0,
+    ],
+    // XX
+    [
+        29, 29, 34, 19, 34, 3, 18, 19, 11, 7, 11, 3, 34, 34, 11, 10, 34, 34, 29, 19, 34, 22, 11,
+        34, 34, 34, 3, 3, 19, 23, 34, 31, 25, 25, 17, 34, 29, 34, 4, 11, 8, 29, 4, 7, 34, 34, 34,
+        34, 34, 34, 34, 34, 34, 34, 34, 34, 0,
+    ],
+    // ZW
+    [
+        5, 5, 5, 5, 5, 3, 5, 5, 5, 5, 5, 3, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 3, 3, 5, 5,
+        5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 4, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 0,
+    ],
+    // ZWJ
+    [
+        6, 6, 6, 6, 6, 3, 6, 6, 6, 6, 6, 3, 6, 6, 6, 6, 6, 6, 6, 6, 6, 6, 6, 6, 6, 6, 3, 3, 6, 6,
+        6, 6, 6, 6, 6, 6, 6, 6, 4, 6, 6, 6, 4, 6, 6, 6, 6, 6, 6, 6, 6, 6, 6, 6, 6, 6, 0,
+    ],
+    // B2_SP
+    [
+        16, 16, 15, 16, 16, 3, 16, 16, 11, 16, 11, 3, 16, 16, 11, 10, 16, 16, 16, 16, 16, 16, 11,
+        16, 16, 16, 3, 3, 16, 16, 16, 16, 16, 16, 16, 16, 16, 16, 15, 11, 8, 16, 4, 16, 16, 16, 16,
+        16, 16, 16, 16, 16, 16, 16, 16, 16, 0,
+    ],
+    // CL_CP_SP
+    [
+        16, 16, 16, 16, 16, 3, 16, 14, 11, 16, 11, 3, 16, 16, 11, 10, 16, 16, 16, 16, 16, 16, 11,
+        16, 16, 16, 3, 3, 14, 16, 16, 16, 16, 16, 16, 16, 16, 16, 14, 11, 8, 16, 4, 16, 16, 16, 16,
+        16, 16, 16, 16, 16, 16, 16, 16, 16, 0,
+    ],
+    // HL_HY
+    [
+        20, 20, 20, 19, 20, 3, 18, 19, 11, 7, 11, 3, 20, 20, 11, 10, 20, 20, 20, 19, 20, 20, 11,
+        20, 20, 20, 3, 3, 19, 20, 20, 20, 20, 20, 17, 20, 20, 20, 4, 11, 8, 20, 4, 7, 20, 20, 20,
+        20, 20, 20, 20, 20, 20, 20, 20, 20, 0,
+    ],
+    // LB25_HY
+    [
+        26, 26, 26, 19, 26, 3, 18, 19, 11, 7, 11, 3, 26, 26, 11, 10, 26, 26, 26, 19, 26, 22, 11,
+        26, 26, 26, 3, 3, 19, 26, 26, 26, 26, 26, 17, 26, 26, 26, 4, 11, 8, 26, 4, 7, 26, 26, 26,
+        26, 26, 26, 26, 26, 26, 26, 26, 26, 26,
+    ],
+    // LB25_OP
+    [
+        26, 26, 26, 19, 26, 3, 18, 19, 11, 7, 11, 3, 26, 26, 11, 10, 26, 26, 26, 19, 26, 22, 11,
+        26, 26, 26, 3, 3, 19, 26, 26, 26, 26, 26, 17, 26, 26, 26, 4, 11, 8, 26, 4, 7, 26, 26, 26,
+        26, 26, 26, 26, 26, 26, 26, 26, 26, 26,
+    ],
+    // LB25_NU_IS
+    [
+        30, 30, 34, 19, 34, 3, 18, 19, 26, 7, 26, 3, 34, 34, 11, 10, 34, 34, 30, 19, 34, 22, 26,
+        34, 34, 34, 3, 3, 19, 26, 34, 34, 26, 26, 17, 34, 30, 34, 4, 26, 8, 30, 4, 7, 34, 34, 34,
+        34, 34, 34, 34, 34, 34, 34, 34, 34, 0,
+    ],
+    // LB25_NU_SY
+    [
+        34, 34, 34, 19, 34, 3, 18, 19, 26, 7, 26, 3, 34, 34, 11, 10, 34, 34, 34, 19, 34, 22, 26,
+        34, 34, 34, 3, 3, 19, 26, 34, 34, 26, 26, 17, 34, 34, 34, 4, 26, 8, 34, 4, 7, 34, 34, 34,
+        34, 34, 34, 34, 34, 34, 34, 34, 34, 0,
+    ],
+    // LB25_NU_CL
+    [
+        34, 34, 34, 19, 34, 3, 18, 14, 11, 7, 11, 3, 34, 34, 11, 10, 34, 34, 34, 19, 34, 22, 11,
+        34, 34, 34, 3, 3, 14, 34, 34, 34, 26, 26, 17, 34, 34, 34, 4, 11, 8, 34, 4, 7, 34, 34, 34,
+        34, 34, 34, 34, 34, 34, 34, 34, 34, 0,
+    ],
+    // LB25_NU_CP
+    [
+        31, 31, 34, 19, 34, 3, 18, 14, 11, 7, 11, 3, 34, 34, 11, 10, 34, 34, 31, 19, 34, 22, 11,
+        34, 34, 34, 3, 3, 14, 31, 34, 34, 26, 26, 17, 34, 31, 34, 4, 11, 8, 31, 4, 7, 34, 34, 34,
+        34, 34, 34, 34, 34, 34, 34, 34, 34, 0,
+    ],
+    // OP_SP
+    [
+        12, 12, 12, 12, 12, 3, 12, 12, 11, 7, 11, 3, 12, 12, 11, 10, 12, 12, 12, 12, 12, 12, 11,
+        12, 12, 12, 3, 3, 12, 12, 12, 12, 12, 12, 12, 12, 12, 12, 12, 11, 8, 12, 4, 7, 12, 12, 12,
+        12, 12, 12, 12, 12, 12, 12, 12, 12, 0,
+    ],
+    // QU_SP
+    [
+        16, 16, 16, 16, 16, 3, 16, 16, 11, 16, 11, 3, 16, 16, 11, 10, 16, 16, 16, 16, 16, 16, 11,
+        16, 16, 16, 3, 3, 16, 16, 13, 13, 16, 16, 16, 16, 16, 16, 13, 11, 8, 16, 4, 16, 16, 16, 16,
+        16, 16, 16, 16, 16, 16, 16, 16, 16, 0,
+    ],
+    // RI_RI
+    [
+        34, 34, 34, 19, 34, 3, 18, 19, 11, 7, 11, 3, 34, 34, 11, 10, 34, 34, 34, 19, 34, 22, 11,
+        34, 34, 34, 3, 3, 19, 34, 34, 34, 34, 34, 17, 32, 34, 34, 4, 11, 8, 34, 4, 7, 34, 34, 34,
+        34, 34, 34, 34, 34, 34, 34, 34, 34, 0,
+    ],
+    // EOT
+    [
+        34, 34, 34, 19, 34, 3, 18, 19, 11, 7, 11, 3, 34, 34, 11, 10, 34, 34, 34, 19, 34, 22, 11,
+        34, 34, 34, 3, 3, 19, 34, 34, 34, 34, 34, 17, 34, 34, 34, 4, 11, 8, 34, 4, 7, 34, 34, 34,
+        34, 34, 34, 34, 34, 34, 34, 34, 34, 0,
+    ],
+];
+
+pub const UAX14_RULE_NAMES: [&str; 35] = [
+    "LB3", "LB4", "LB5", "LB6", "LB7", "LB8", "LB8a", "LB9", "LB11", "LB12", "LB12a", "LB13",
+    "LB14", "LB15", "LB16", "LB17", "LB18", "LB19", "LB20", "LB21", "LB21a", "LB21b", "LB22",
+    "LB23", "LB23a", "LB24", "LB25", "LB26", "LB27", "LB28", "LB29", "LB30", "LB30a", "LB30b",
+    "LB31",
+];
@@ -0,0 +1,17 @@
+// Thai characters for the cluster boundaries of the dictionary-based segmenter. Thai words are
+// found by the LSTM model or a dictionary, not by these rules.
+
+// Vowels written before the consonant: U+0E40..U+0E44
+fn is_preposed_vowel(c: u16) -> bool {
+    (0xe40..=0xe44).contains(&c)
+}
+
+// Vowels, tone marks and signs written after, above or below the consonant, and MAIYAMOK
+// (U+0E46) that repeats the previous word.
+fn is_dependent(c: u16) -> bool {
+    (0xe30..=0xe3a).contains(&c) || (0xe45..=0xe4e).contains(&c)
+}
+
+pub fn is_cluster_boundary(input: &[u16], offset: usize) -> bool {
+    !(is_preposed_vowel(input[offset - 1]) || is_dependent(input[offset]))
+}
@@ -0,0 +1,874 @@
+//! Line wrapping on top of the line break iterator. [`wrap`] fills each line greedily, and
+//! [`wrap_optimal`] chooses the lines of a whole paragraph together like TeX. [`wrap_columns`]
+//! wraps text for a terminal.
+//!
+//! ```rust
+//! use uax14_rs::wrap::{wrap, TrailingWhitespace};
+//! use uax14_rs::LineBreakOptions;
+//!
+//! let text = "The quick brown fox jumps over the lazy dog.";
+//! let lines = wrap(
+//!     text,
+//!     &LineBreakOptions::default(),
+//!     10,
+//!     TrailingWhitespace::Trim,
+//!     |s| s.chars().count(),
+//! );
+//! let lines: Vec<&str> = lines.into_iter().map(|r| &text[r]).collect();
+//! assert_eq!(lines, ["The quick", "brown fox", "jumps over", "the lazy", "dog."]);
+//! ```
+
+use crate::line_break_class;
+use crate::BreakKind;
+use crate::LineBreakClass;
+use crate::LineBreakIterator;
+use crate::LineBreakOptions;
+
+use core::ops::Range;
+use unicode_width::UnicodeWidthStr;
+
+/// How the white space at the end of a line is handled.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TrailingWhitespace {
+    /// Spaces, tabs and the line terminator at the end of a line are kept in the line, but
+    /// aren't measured. This is `white-space: pre-wrap;` in CSS.
+    Hang,
+    /// Spaces, tabs and the line terminator at the end of a line are removed from the line.
+    Trim,
+}
+
+/// Wrap `text` into lines that are `width` or narrower, and return their byte ranges.
+///
+/// `measure` returns the width of a part of the text. Lines are broken at the first break
+/// opportunity that doesn't fit, and always at mandatory breaks. When a unit between two break
+/// opportunities is wider than `width` by itself, it is broken between code points, but not
+/// before combining marks and ZWJ.
+pub fn wrap<W, F>(
+    text: &str,
+    options: &LineBreakOptions,
+    width: W,
+    trailing_whitespace: TrailingWhitespace,
+    mut measure: F,
+) -> Vec<Range<usize>>
+where
+    W: PartialOrd,
+    F: FnMut(&str) -> W,
+{
+    let mut lines = Vec::new();
+    let mut line_start = 0;
+    // The end of the last segment in the current line, and the end without white space.
+    let mut line_end = 0;
+    let mut content_end = 0;
+    let push_line = |lines: &mut Vec<Range<usize>>, start: usize, end: usize, content: usize| {
+        match trailing_whitespace {
+            TrailingWhitespace::Hang => lines.push(start..end),
+            TrailingWhitespace::Trim => lines.push(start..content),
+        }
+    };
+
+    for segment in LineBreakIterator::new_with_options(text, options).segments() {
+        let segment_content_end = segment.trailing_whitespace.start;
+        if line_end > line_start && measure(&text[line_start..segment_content_end]) > width {
+            push_line(&mut lines, line_start, line_end, content_end);
+            line_start = line_end;
+        }
+
+        // Emergency breaks when the segment doesn't fit in an empty line.
+        while line_start < segment_content_end
+            && measure(&text[line_start..segment_content_end]) > width
+        {
+            let mut end = None;
+            for (index, c) in text[line_start..segment_content_end].char_indices() {
+                let offset = line_start + index;
+                if offset == line_start || !is_emergency_break_before(c) {
+                    continue;
+                }
+                if end.is_some() && measure(&text[line_start..offset]) > width {
+                    break;
+                }
+                end = Some(offset);
+            }
+            let end = match end {
+                Some(end) => end,
+                None => break,
+            };
+            lines.push(line_start..end);
+            line_start = end;
+        }
+
+        line_end = segment.range.end;
+        content_end = segment_content_end;
+        if segment.kind == BreakKind::Mandatory {
+            push_line(&mut lines, line_start, line_end, content_end);
+            line_start = line_end;
+        }
+    }
+    if line_end > line_start {
+        push_line(&mut lines, line_start, line_end, content_end);
+    }
+    lines
+}
+
+/// The width of characters whose East Asian width is ambiguous, such as box drawing characters.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum AmbiguousWidth {
+    /// One column, as in most terminals.
+    Narrow,
+    /// Two columns, as in terminals for Chinese, Japanese and Korean.
+    Wide,
+}
+
+/// Return the width of `text` in terminal columns. Wide characters take two columns, and
+/// combining marks and other zero width characters take none.
+pub fn column_width(text: &str, ambiguous_width: AmbiguousWidth) -> usize {
+    match ambiguous_width {
+        AmbiguousWidth::Narrow => text.width(),
+        AmbiguousWidth::Wide => text.width_cjk(),
+    }
+}
+
+/// Wrap `text` into lines of `columns` or fewer terminal columns, and return their byte ranges
+/// without the trailing white space. This is [`wrap`] measured by [`column_width`].
+///
+/// ```rust
+/// use uax14_rs::wrap::{wrap_columns, AmbiguousWidth};
+/// use uax14_rs::LineBreakOptions;
+///
+/// let text = "Usage: 使い方を表示します";
+/// let lines = wrap_columns(text, &LineBreakOptions::default(), 12, AmbiguousWidth::Narrow);
+/// let lines: Vec<&str> = lines.into_iter().map(|r| &text[r]).collect();
+/// assert_eq!(lines, ["Usage: 使い", "方を表示しま", "す"]);
+/// ```
+pub fn wrap_columns(
+    text: &str,
+    options: &LineBreakOptions,
+    columns: usize,
+    ambiguous_width: AmbiguousWidth,
+) -> Vec<Range<usize>> {
+    wrap(text, options, columns, TrailingWhitespace::Trim, |s| {
+        column_width(s, ambiguous_width)
+    })
+}
+
+/// Wrap `text` like [`wrap_columns`], and return the lines as strings.
+pub fn wrap_columns_to_strings(
+    text: &str,
+    options: &LineBreakOptions,
+    columns: usize,
+    ambiguous_width: AmbiguousWidth,
+) -> Vec<String> {
+    wrap_columns(text, options, columns, ambiguous_width)
+        .into_iter()
+        .map(|r| text[r].to_string())
+        .collect()
+}
+
+/// Options of [`wrap_optimal`]. Widths are in the unit of the `measure` function, and the
+/// penalties and demerits are the ones of TeX.
+#[derive(Clone, Debug)]
+pub struct ParagraphOptions {
+    line_widths: Vec<f64>,
+    tolerance: f64,
+    line_penalty: f64,
+    hyphen_penalty: f64,
+    cjk_penalty: f64,
+    fitness_demerits: f64,
+    space_stretch: f64,
+    space_shrink: f64,
+}
+
+impl ParagraphOptions {
+    /// Create options for lines of `width`, with a tolerance of 200, a line penalty of 10, a
+    /// hyphen penalty of 50, no CJK penalty and fitness demerits of 10000. Spaces stretch by half
+    /// of their width and shrink by a third of it.
+    pub fn new(width: f64) -> ParagraphOptions {
+        ParagraphOptions {
+            line_widths: vec![width],
+            tolerance: 200.0,
+            line_penalty: 10.0,
+            hyphen_penalty: 50.0,
+            cjk_penalty: 0.0,
+            fitness_demerits: 10000.0,
+            space_stretch: 0.5,
+            space_shrink: 1.0 / 3.0,
+        }
+    }
+
+    /// Set the width of each line. The last width is used for the rest of the lines.
+    pub fn line_widths(mut self, line_widths: &[f64]) -> ParagraphOptions {
+        assert!(!line_widths.is_empty(), "no line widths");
+        self.line_widths = line_widths.to_vec();
+        self
+    }
+
+    /// Set the largest badness of a line. When the text can't be broken into such lines, lines
+    /// of any badness are used, and a unit wider than a line overflows it.
+    pub fn tolerance(mut self, tolerance: f64) -> ParagraphOptions {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Set the penalty of each line, which prefers fewer lines
+    pub fn line_penalty(mut self, line_penalty: f64) -> ParagraphOptions {
+        self.line_penalty = line_penalty;
+        self
+    }
+
+    /// Set the penalty of a break after a hyphen or a soft hyphen
+    pub fn hyphen_penalty(mut self, hyphen_penalty: f64) -> ParagraphOptions {
+        self.hyphen_penalty = hyphen_penalty;
+        self
+    }
+
+    /// Set the penalty of a break between ideographs, kana or Hangul without spaces
+    pub fn cjk_penalty(mut self, cjk_penalty: f64) -> ParagraphOptions {
+        self.cjk_penalty = cjk_penalty;
+        self
+    }
+
+    /// Set the demerits of two adjacent lines whose spaces are adjusted very differently, such
+    /// as a tight line after a loose one
+    pub fn fitness_demerits(mut self, fitness_demerits: f64) -> ParagraphOptions {
+        self.fitness_demerits = fitness_demerits;
+        self
+    }
+
+    /// Set how much spaces stretch and shrink in proportion to their width
+    pub fn space_adjustment(mut self, stretch: f64, shrink: f64) -> ParagraphOptions {
+        self.space_stretch = stretch;
+        self.space_shrink = shrink;
+        self
+    }
+
+    fn get_line_width(&self, line: usize) -> f64 {
+        self.line_widths[line.min(self.line_widths.len() - 1)]
+    }
+}
+
+/// A line chosen by [`wrap_optimal`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct OptimalLine {
+    /// The byte range of the line.
+    pub range: Range<usize>,
+    /// How far the spaces are from their natural width, from 0 to 10000, as in TeX.
+    pub badness: f64,
+    /// The adjustment of the spaces in proportion to how much they stretch (when positive) or
+    /// shrink (when negative). This is 0 for the last line of a paragraph that fits, infinite
+    /// for a short line without spaces, and below -1 for a line that overflows.
+    pub ratio: f64,
+}
+
+const INFINITE_BADNESS: f64 = 10000.0;
+
+// A feasible breakpoint: the start of the text, or the end of a segment.
+struct Breakpoint {
+    end: usize,
+    content_end: usize,
+    penalty: f64,
+    forced: bool,
+    // The total width, stretch and shrink of the spaces at the breakpoints up to this one.
+    total_glue: (f64, f64, f64),
+}
+
+struct Node {
+    breakpoint: usize,
+    line: usize,
+    fitness: usize,
+    demerits: f64,
+    badness: f64,
+    ratio: f64,
+    previous: Option<usize>,
+}
+
+/// Break `text` into the lines that minimize the total demerits of the paragraph, by the
+/// algorithm of Knuth and Plass, and return them with their badness and adjustment ratio.
+///
+/// The break opportunities of the line break iterator are the feasible breakpoints, and
+/// mandatory breaks end paragraphs. `measure` returns the natural width of a part of the text.
+///
+/// When `measure` returns NaN or an infinite width, the text is treated as infinitely wide. A
+/// line of it overflows like a unit wider than the line, and such spaces don't stretch or shrink.
+///
+/// ```rust
+/// use uax14_rs::wrap::{wrap_optimal, ParagraphOptions, TrailingWhitespace};
+/// use uax14_rs::LineBreakOptions;
+///
+/// let text = "a b c d e f g h i j k l m n o p q r s t u v w x y z";
+/// let lines = wrap_optimal(
+///     text,
+///     &LineBreakOptions::default(),
+///     &ParagraphOptions::new(10.0),
+///     TrailingWhitespace::Trim,
+///     |s| s.chars().count() as f64,
+/// );
+/// let lines: Vec<&str> = lines.into_iter().map(|l| &text[l.range]).collect();
+/// assert_eq!(lines, ["a b c d e", "f g h i j k", "l m n o p", "q r s t u", "v w x y z"]);
+/// ```
+pub fn wrap_optimal<F>(
+    text: &str,
+    options: &LineBreakOptions,
+    paragraph_options: &ParagraphOptions,
+    trailing_whitespace: TrailingWhitespace,
+    mut measure: F,
+) -> Vec<OptimalLine>
+where
+    F: FnMut(&str) -> f64,
+{
+    let mut measure = |s: &str| {
+        let width = measure(s);
+        if width.is_finite() {
+            width
+        } else {
+            f64::INFINITY
+        }
+    };
+    let mut breakpoints = vec![Breakpoint {
+        end: 0,
+        content_end: 0,
+        penalty: 0.0,
+        forced: false,
+        total_glue: (0.0, 0.0, 0.0),
+    }];
+    let mut segments = LineBreakIterator::new_with_options(text, options)
+        .segments()
+        .peekable();
+    while let Some(segment) = segments.next() {
+        let content_end = segment.trailing_whitespace.start;
+        let forced = segment.kind == BreakKind::Mandatory || segments.peek().is_none();
+        let (mut width, mut stretch, mut shrink) = breakpoints.last().unwrap().total_glue;
+        if !forced && content_end < segment.range.end {
+            let glue = measure(&text[content_end..segment.range.end]);
+            // An infinite sum can't be subtracted to find the glue of a line.
+            let glue = if glue.is_finite() { glue } else { 0.0 };
+            width += glue;
+            stretch += glue * paragraph_options.space_stretch;
+            shrink += glue * paragraph_options.space_shrink;
+        }
+        let penalty = if forced {
+            0.0
+        } else if is_hyphen_break(&text[..content_end]) {
+            paragraph_options.hyphen_penalty
+        } else if content_end == segment.range.end && is_cjk_break(text, content_end) {
+            paragraph_options.cjk_penalty
+        } else {
+            0.0
+        };
+        breakpoints.push(Breakpoint {
+            end: segment.range.end,
+            content_end,
+            penalty,
+            forced,
+            total_glue: (width, stretch, shrink),
+        });
+    }
+    if breakpoints.len() == 1 {
+        return Vec::new();
+    }
+
+    let nodes = find_optimal_breaks(
+        text,
+        &breakpoints,
+        paragraph_options,
+        paragraph_options.tolerance,
+        false,
+        &mut measure,
+    )
+    .unwrap_or_else(|| {
+        find_optimal_breaks(
+            text,
+            &breakpoints,
+            paragraph_options,
+            f64::INFINITY,
+            true,
+            &mut measure,
+        )
+        .unwrap()
+    });
+
+    let mut lines = Vec::new();
+    let mut index = nodes
+        .iter()
+        .enumerate()
+        .filter(|(_, node)| node.breakpoint == breakpoints.len() - 1)
+        .min_by(|(_, a), (_, b)| a.demerits.partial_cmp(&b.demerits).unwrap())
+        .map(|(index, _)| index);
+    while let Some(node) = index.map(|index| &nodes[index]) {
+        let previous = match node.previous {
+            Some(previous) => previous,
+            None => break,
+        };
+        let start = breakpoints[nodes[previous].breakpoint].end;
+        let breakpoint = &breakpoints[node.breakpoint];
+        let end = match trailing_whitespace {
+            TrailingWhitespace::Hang => breakpoint.end,
+            TrailingWhitespace::Trim => breakpoint.content_end,
+        };
+        lines.push(OptimalLine {
+            range: start..end,
+            badness: node.badness,
+            ratio: node.ratio,
+        });
+        index = Some(previous);
+    }
+    lines.reverse();
+    lines
+}
+
+// Return all the nodes of the paragraph, or `None` when no lines are within `tolerance`. With
+// `overflow`, a line overflows when there is no other way to continue.
+fn find_optimal_breaks<F>(
+    text: &str,
+    breakpoints: &[Breakpoint],
+    options: &ParagraphOptions,
+    tolerance: f64,
+    overflow: bool,
+    measure: &mut F,
+) -> Option<Vec<Node>>
+where
+    F: FnMut(&str) -> f64,
+{
+    let mut nodes = vec![Node {
+        breakpoint: 0,
+        line: 0,
+        fitness: 1,
+        demerits: 0.0,
+        badness: 0.0,
+        ratio: 0.0,
+        previous: None,
+    }];
+    let mut active = vec![0];
+
+    for (index, breakpoint) in breakpoints.iter().enumerate().skip(1) {
+        let mut candidates: Vec<Node> = Vec::new();
+        // The node that overflows the least, which is the latest one that can't reach this
+        // breakpoint with the fewest demerits.
+        let mut overflowed: Option<(usize, f64)> = None;
+        let mut i = 0;
+        while i < active.len() {
+            let node_index = active[i];
+            let node = &nodes[node_index];
+            let width = options.get_line_width(node.line);
+            let natural = measure(&text[breakpoints[node.breakpoint].end..breakpoint.content_end]);
+            let total_glue = breakpoints[index - 1].total_glue;
+            let node_glue = breakpoints[node.breakpoint].total_glue;
+            let stretch = total_glue.1 - node_glue.1;
+            let shrink = total_glue.2 - node_glue.2;
+            let ratio = if natural > width {
+                if shrink > 0.0 {
+                    (width - natural) / shrink
+                } else {
+                    f64::NEG_INFINITY
+                }
+            } else if breakpoint.forced || natural == width {
+                0.0
+            } else if stretch > 0.0 {
+                (width - natural) / stretch
+            } else {
+                f64::INFINITY
+            };
+
+            if ratio < -1.0 || breakpoint.forced {
+                active.remove(i);
+            } else {
+                i += 1;
+            }
+            if ratio < -1.0 {
+                let better = match overflowed {
+                    Some((previous, _)) => {
+                        let previous = &nodes[previous];
+                        node.breakpoint > previous.breakpoint
+                            || (node.breakpoint == previous.breakpoint
+                                && node.demerits < previous.demerits)
+                    }
+                    None => true,
+                };
+                if better {
+                    overflowed = Some((node_index, ratio));
+                }
+                continue;
+            }
+            let badness = (100.0 * ratio.abs().powi(3)).min(INFINITE_BADNESS);
+            if badness > tolerance {
+                continue;
+            }
+            let fitness = get_fitness_class(ratio);
+            let mut demerits = (options.line_penalty + badness).powi(2);
+            if breakpoint.penalty >= 0.0 {
+                demerits += breakpoint.penalty.powi(2);
+            } else {
+                demerits -= breakpoint.penalty.powi(2);
+            }
+            if (fitness as isize - node.fitness as isize).abs() > 1 {
+                demerits += options.fitness_demerits;
+            }
+            add_candidate(
+                &mut candidates,
+                options,
+                Node {
+                    breakpoint: index,
+                    line: node.line + 1,
+                    fitness,
+                    demerits: node.demerits + demerits,
+                    badness,
+                    ratio,
+                    previous: Some(node_index),
+                },
+            );
+        }
+
+        if candidates.is_empty() && (active.is_empty() || breakpoint.forced) {
+            let (previous, ratio) = match overflowed {
+                Some(overflowed) if overflow => overflowed,
+                _ => return None,
+            };
+            let node = &nodes[previous];
+            candidates.push(Node {
+                breakpoint: index,
+                line: node.line + 1,
+                fitness: 0,
+                demerits: node.demerits + (options.line_penalty + INFINITE_BADNESS).powi(2),
+                badness: INFINITE_BADNESS,
+                ratio,
+                previous: Some(previous),
+            });
+        }
+        for node in candidates {
+            active.push(nodes.len());
+            nodes.push(node);
+        }
+    }
+    Some(nodes)
+}
+
+// Keep the candidate with the fewest demerits for each fitness class and width of the next line.
+fn add_candidate(candidates: &mut Vec<Node>, options: &ParagraphOptions, node: Node) {
+    let last_width = options.line_widths.len() - 1;
+    let same = candidates
+        .iter_mut()
+        .find(|c| c.fitness == node.fitness && c.line.min(last_width) == node.line.min(last_width));
+    match same {
+        Some(same) if same.demerits <= node.demerits => {}
+        Some(same) => *same = node,
+        None => candidates.push(node),
+    }
+}
+
+// The fitness classes of TeX: tight, decent, loose and very loose.
+fn get_fitness_class(ratio: f64) -> usize {
+    if ratio < -0.5 {
+        0
+    } else if ratio <= 0.5 {
+        1
+    } else if ratio <= 1.0 {
+        2
+    } else {
+        3
+    }
+}
+
+fn is_hyphen_break(before: &str) -> bool {
+    match before.chars().next_back() {
+        Some(c) => {
+            c == '\u{00AD}' || c == '\u{2010}' || line_break_class(c) == LineBreakClass::Hyphen
+        }
+        None => false,
+    }
+}
+
+fn is_cjk_break(text: &str, offset: usize) -> bool {
+    let is_cjk = |c: Option<char>| match c {
+        Some(c) => matches!(
+            line_break_class(c),
+            LineBreakClass::Ideographic
+                | LineBreakClass::ConditionalJapaneseStarter
+                | LineBreakClass::H2
+                | LineBreakClass::H3
+                | LineBreakClass::JL
+                | LineBreakClass::JV
+                | LineBreakClass::JT
+        ),
+        None => false,
+    };
+    is_cjk(text[..offset].chars().next_back()) || is_cjk(text[offset..].chars().next())
+}
+
+fn is_emergency_break_before(c: char) -> bool {
+    !matches!(
+        line_break_class(c),
+        LineBreakClass::CombiningMark | LineBreakClass::ZWJ
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::wrap::{
+        column_width, wrap, wrap_columns_to_strings, wrap_optimal, AmbiguousWidth, OptimalLine,
+        ParagraphOptions, TrailingWhitespace,
+    };
+    use crate::LineBreakOptions;
+    use crate::WordBreakRule;
+    use core::ops::Range;
+
+    fn wrap_chars(text: &str, width: usize, trailing_whitespace: TrailingWhitespace) -> Vec<&str> {
+        let lines: Vec<Range<usize>> = wrap(
+            text,
+            &LineBreakOptions::default(),
+            width,
+            trailing_whitespace,
+            |s| s.chars().count(),
+        );
+        lines.into_iter().map(|r| &text[r]).collect()
+    }
+
+    #[test]
+    fn wrap_greedy() {
+        let text = "Lorem ipsum dolor sit amet,  consectetur";
+        assert_eq!(
+            wrap_chars(text, 12, TrailingWhitespace::Trim),
+            ["Lorem ipsum", "dolor sit", "amet,", "consectetur"]
+        );
+        assert_eq!(
+            wrap_chars(text, 12, TrailingWhitespace::Hang),
+            ["Lorem ipsum ", "dolor sit ", "amet,  ", "consectetur"]
+        );
+        assert_eq!(wrap_chars(text, 100, TrailingWhitespace::Hang), [text]);
+        assert!(wrap_chars("", 10, TrailingWhitespace::Trim).is_empty());
+    }
+
+    #[test]
+    fn wrap_mandatory() {
+        let text = "first line\r\nsecond\n\nthird";
+        assert_eq!(
+            wrap_chars(text, 20, TrailingWhitespace::Trim),
+            ["first line", "second", "", "third"]
+        );
+        assert_eq!(
+            wrap_chars(text, 20, TrailingWhitespace::Hang),
+            ["first line\r\n", "second\n", "\n", "third"]
+        );
+    }
+
+    #[test]
+    fn wrap_emergency() {
+        assert_eq!(
+            wrap_chars("a supercalifragilistic word", 8, TrailingWhitespace::Trim),
+            ["a", "supercal", "ifragili", "stic", "word"]
+        );
+        // Combining marks aren't separated from the base character.
+        assert_eq!(
+            wrap_chars("e\u{301}e\u{301}e\u{301}", 3, TrailingWhitespace::Trim),
+            ["e\u{301}", "e\u{301}", "e\u{301}"]
+        );
+        // A character that is wider than the line is put in a line by itself.
+        assert_eq!(
+            wrap_chars("abc", 0, TrailingWhitespace::Trim),
+            ["a", "b", "c"]
+        );
+    }
+
+    #[test]
+    fn wrap_options() {
+        let text = "日本語のテキスト";
+        let options = LineBreakOptions::new().word_break_rule(WordBreakRule::KeepAll);
+        let lines = wrap(text, &options, 6, TrailingWhitespace::Trim, |s| {
+            s.chars().count() * 2
+        });
+        let lines: Vec<&str> = lines.into_iter().map(|r| &text[r]).collect();
+        assert_eq!(lines, ["日本語", "のテキ", "スト"]);
+    }
+
+    fn wrap_paragraph<'a>(text: &'a str, options: &ParagraphOptions) -> Vec<&'a str> {
+        let lines: Vec<OptimalLine> = wrap_optimal(
+            text,
+            &LineBreakOptions::default(),
+            options,
+            TrailingWhitespace::Trim,
+            |s| s.chars().count() as f64,
+        );
+        lines.into_iter().map(|l| &text[l.range]).collect()
+    }
+
+    #[test]
+    fn wrap_optimal_paragraph() {
+        let text = "a b c d e f g h i j k l m n o p q r s t u v w x y z";
+        let lines = wrap_optimal(
+            text,
+            &LineBreakOptions::default(),
+            &ParagraphOptions::new(10.0),
+            TrailingWhitespace::Hang,
+            |s| s.chars().count() as f64,
+        );
+        let expected = [
+            ("a b c d e ", 0.5),
+            ("f g h i j k ", -0.6),
+            ("l m n o p ", 0.5),
+            ("q r s t u ", 0.5),
+            ("v w x y z", 0.0),
+        ];
+        assert_eq!(lines.len(), expected.len());
+        for (line, (s, ratio)) in lines.iter().zip(expected.iter()) {
+            assert_eq!(&text[line.range.clone()], *s);
+            assert!((line.ratio - ratio).abs() < 1e-9, "{:?}", line);
+            assert!(
+                (line.badness - 100.0 * ratio.abs().powi(3)).abs() < 1e-9,
+                "{:?}",
+                line
+            );
+        }
+        // The greedy lines leave "z" alone.
+        assert_eq!(wrap_chars(text, 10, TrailingWhitespace::Trim).len(), 6);
+
+        // Each mandatory break ends a paragraph.
+        assert_eq!(
+            wrap_paragraph("a b\nc d e f g h i j", &ParagraphOptions::new(6.0)),
+            ["a b", "c d e f", "g h i j"]
+        );
+        assert!(wrap_paragraph("", &ParagraphOptions::new(6.0)).is_empty());
+    }
+
+    #[test]
+    fn wrap_optimal_line_widths() {
+        let text = "a b c d e f g h i j k l m n o p q r s t u v w x y z";
+        let options = ParagraphOptions::new(10.0).line_widths(&[4.0, 6.0, 10.0]);
+        assert_eq!(
+            wrap_paragraph(text, &options),
+            [
+                "a b",
+                "c d e",
+                "f g h i j",
+                "k l m n o",
+                "p q r s t u",
+                "v w x y z"
+            ]
+        );
+    }
+
+    #[test]
+    fn wrap_optimal_penalties() {
+        let options = ParagraphOptions::new(12.0).tolerance(10000.0);
+        let text = "aa bb cc dd-ee ff gg";
+        assert_eq!(wrap_paragraph(text, &options), ["aa bb cc dd-", "ee ff gg"]);
+        assert_eq!(
+            wrap_paragraph(text, &options.clone().hyphen_penalty(10000.0)),
+            ["aa bb cc", "dd-ee ff gg"]
+        );
+
+        let options = ParagraphOptions::new(7.0).tolerance(10000.0);
+        let text = "aa bb 日本語";
+        assert_eq!(wrap_paragraph(text, &options), ["aa bb 日", "本語"]);
+        assert_eq!(
+            wrap_paragraph(text, &options.clone().cjk_penalty(10000.0)),
+            ["aa bb", "日本語"]
+        );
+    }
+
+    #[test]
+    fn wrap_optimal_non_finite_width() {
+        // A word of NaN or infinite width overflows a line by itself.
+        for &invalid in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY].iter() {
+            let text = "Hello world again";
+            let lines = wrap_optimal(
+                text,
+                &LineBreakOptions::default(),
+                &ParagraphOptions::new(10.0),
+                TrailingWhitespace::Trim,
+                |s| {
+                    if s.contains('w') {
+                        invalid
+                    } else {
+                        s.chars().count() as f64
+                    }
+                },
+            );
+            let lines: Vec<(&str, f64)> = lines
+                .into_iter()
+                .map(|l| (&text[l.range], l.ratio))
+                .collect();
+            assert_eq!(lines.len(), 3, "{}", invalid);
+            assert_eq!(lines[0].0, "Hello");
+            assert_eq!(lines[1], ("world", f64::NEG_INFINITY));
+            assert_eq!(lines[2], ("again", 0.0));
+        }
+
+        // Lines with spaces of infinite width overflow, and the spaces don't stretch.
+        let text = "a b c";
+        let lines = wrap_optimal(
+            text,
+            &LineBreakOptions::default(),
+            &ParagraphOptions::new(3.0),
+            TrailingWhitespace::Trim,
+            |s| {
+                if s.contains(' ') {
+                    f64::INFINITY
+                } else {
+                    s.len() as f64
+                }
+            },
+        );
+        let lines: Vec<&str> = lines.into_iter().map(|l| &text[l.range]).collect();
+        assert_eq!(lines, ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn wrap_optimal_overflow() {
+        let text = "a supercalifragilistic word";
+        let lines = wrap_optimal(
+            text,
+            &LineBreakOptions::default(),
+            &ParagraphOptions::new(8.0),
+            TrailingWhitespace::Trim,
+            |s| s.chars().count() as f64,
+        );
+        let lines: Vec<(&str, f64)> = lines
+            .into_iter()
+            .map(|l| (&text[l.range], l.ratio))
+            .collect();
+        assert_eq!(
+            lines,
+            [
+                ("a", f64::INFINITY),
+                ("supercalifragilistic", f64::NEG_INFINITY),
+                ("word", 0.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn wrap_terminal_columns() {
+        assert_eq!(column_width("日本語", AmbiguousWidth::Narrow), 6);
+        assert_eq!(column_width("e\u{301}", AmbiguousWidth::Narrow), 1);
+        assert_eq!(column_width("\u{2500}\u{2500}", AmbiguousWidth::Narrow), 2);
+        assert_eq!(column_width("\u{2500}\u{2500}", AmbiguousWidth::Wide), 4);
+
+        let options = LineBreakOptions::default();
+        let text = "-h, --help  Print help information";
+        assert_eq!(
+            wrap_columns_to_strings(text, &options, 16, AmbiguousWidth::Narrow),
+            ["-h, --help", "Print help", "information"]
+        );
+        assert_eq!(
+            wrap_columns_to_strings("日本語のテキスト", &options, 7, AmbiguousWidth::Narrow),
+            ["日本語", "のテキ", "スト"]
+        );
+        // Combining marks don't take any column.
+        assert_eq!(
+            wrap_columns_to_strings(
+                "cafe\u{301} cafe\u{301}",
+                &options,
+                4,
+                AmbiguousWidth::Narrow
+            ),
+            ["cafe\u{301}", "cafe\u{301}"]
+        );
+
+        let text = "\u{2500}\u{2500}\u{2500} \u{2500}\u{2500}\u{2500}";
+        assert_eq!(
+            wrap_columns_to_strings(text, &options, 7, AmbiguousWidth::Narrow),
+            [text]
+        );
+        assert_eq!(
+            wrap_columns_to_strings(text, &options, 7, AmbiguousWidth::Wide),
+            ["\u{2500}\u{2500}\u{2500}", "\u{2500}\u{2500}\u{2500}"]
+        );
+    }
+}
@@ -6,9 +6,23 @@ import subprocess
 
 prop = []
 rule = []
+rule_id = []
 table = []
 ea_table = []
 
+# UAX14 rules that can decide a break between two characters.
+rule_names = ["LB3", "LB4", "LB5", "LB6", "LB7", "LB8", "LB8a", "LB9", "LB11",
+              "LB12", "LB12a", "LB13", "LB14", "LB15", "LB16", "LB17", "LB18",
+              "LB19", "LB20", "LB21", "LB21a", "LB21b", "LB22", "LB23", "LB23a",
+              "LB24", "LB25", "LB26", "LB27", "LB28", "LB29", "LB30", "LB30a",
+              "LB30b", "LB31"]
+
+
+def add_rule(value, name):
+    rule.append(value)
+    rule_id.append(rule_names.index(name))
+
+
 for x in range(0x20000):
     prop.append('XX')
     ea_table.append('N')
@@ -121,70 +135,70 @@ for i in prop_type:
         # LB3
         if j == "EOT":
             if i in ("LB25_OP", "LB25_HY"):
-                rule.append("f") # failed. Previous is break.
+                add_rule("f", "LB25") # failed. Previous is break.
                 continue
-            rule.append("!")
+            add_rule("!", "LB3")
             continue
 
         # LB4
         if i == "BK":
-            rule.append("!")
+            add_rule("!", "LB4")
             continue
 
         # LB5
         if i == "CR" and j == "LF":
-            rule.append("x")
+            add_rule("x", "LB5")
             continue
         if i in ("CR", "LF", "NL"):
-            rule.append("!")
+            add_rule("!", "LB5")
             continue
 
         # LB6
         if j in ("BK", "CR", "LF", "NL"):
-            rule.append("x")
+            add_rule("x", "LB6")
             continue
 
         # LB7
         if j == "SP":
             # (LB8)
             if i == "ZW":
-                rule.append(i)
+                add_rule(i, "LB8")
                 continue
             # (LB14)
             if i in ("OP_OP30", "OP_EA", "OP_SP"):
-                rule.append("OP_SP")
+                add_rule("OP_SP", "LB14")
                 continue
             # (LB15)
             if i in ("QU", "QU_SP"):
-                rule.append("QU_SP")
+                add_rule("QU_SP", "LB15")
                 continue
             # (LB16)
             if i in ("CL", "CP", "CP_EA", "CL_CP_SP"):
-                rule.append("CL_CP_SP")
+                add_rule("CL_CP_SP", "LB16")
                 continue
             # (LB17)
             if i in ("B2", "B2_SP"):
-                rule.append("B2_SP")
+                add_rule("B2_SP", "LB17")
                 continue
 
         if j in ("SP", "ZW"):
-            rule.append("x")
+            add_rule("x", "LB7")
             continue
 
         # LB8
         if i in ("ZW"):
-            rule.append("/")
+            add_rule("/", "LB8")
             continue
 
         # LB8a
         if i == "ZWJ":
-            rule.append("x")
+            add_rule("x", "LB8a")
             continue
 
         # LB9
         if (i not in ("BK", "CR", "LF", "NL", "SP", "ZW", "B2_SP", "QU_SP",
                      "CL_CP_SP")) and j in ("CM", "ZWJ"):
-            rule.append(i)
+            add_rule(i, "LB9")
             continue
 
         # LB10
@@ -197,23 +211,23 @@ for i in prop_type:
 
         # LB11
         if i == "WJ":
-            rule.append("x")
+            add_rule("x", "LB11")
             continue
         if j == "WJ":
-            rule.append("x")
+            add_rule("x", "LB11")
             continue
 
         # LB12
         if i == "GL":
-            rule.append("x")
+            add_rule("x", "LB12")
             continue
 
         # LB12a
         if j == "GL":
             if i in ("B2_SP", "CL_CP_SP", "QU_SP", "SP", "BA", "HY"):
-                rule.append("/")
+                add_rule("/", "LB12a")
                 continue
-            rule.append("x")
+            add_rule("x", "LB12a")
             continue
 
         # LB13
@@ -221,140 +235,140 @@ for i in prop_type:
             # LB25 rule.
             pass
         elif j in ("CL", "CP", "CP_EA", "EX", "IS", "SY"):
-            rule.append("x")
+            add_rule("x", "LB13")
             continue
 
         # LB14
         if i in ("OP_OP30", "OP_EA", "OP_SP"):
-            rule.append("x")
+            add_rule("x", "LB14")
             continue
 
         # LB15
         if i in ("QU", "QU_SP") and j in ("OP_OP30", "OP_EA"):
-            rule.append("x")
+            add_rule("x", "LB15")
             continue
         if i == "QU_SP":
             i = "SP"
 
         # LB 16
         if i in ("CL", "CP", "CP_EA", "CL_CP_SP", "LB25_NU_CL", "LB25_NU_CP") and j == "NS":
-            rule.append("x")
+            add_rule("x", "LB16")
             continue
         if i == "CL_CP_SP":
             i = "SP"
 
         # LB17
         if i in ("B2", "B2_SP") and j == "B2":
-            rule.append("x")
+            add_rule("x", "LB17")
             continue
         if i == "B2_SP":
             i = "SP"
 
         # LB18
         if i == "SP":
-            rule.append("/")
+            add_rule("/", "LB18")
             continue
 
         # LB19
         if i == "QU":
-            rule.append("x")
+            add_rule("x", "LB19")
             continue
         if j == "QU":
-            rule.append("x")
+            add_rule("x", "LB19")
             continue
 
         # LB20
         if i == "CB":
-            rule.append("/")
+            add_rule("/", "LB20")
             continue
         if j == "CB":
-            rule.append("/")
+            add_rule("/", "LB20")
             continue
 
         # LB21
         # (LB21a)
         if i == "HL" and j in ("HY", "BA"):
-            rule.append("HL_HY")
+            add_rule("HL_HY", "LB21a")
             continue
         if j in ("BA", "HY", "NS"):
-            rule.append("x")
+            add_rule("x", "LB21")
             continue
         if i == "BB":
-            rule.append("x")
+            add_rule("x", "LB21")
             continue
 
         # LB21a
         if i == "HL_HY":
-            rule.append("x")
+            add_rule("x", "LB21a")
             continue
 
         # LB21b
         if i == "SY" and j == "HL":
-            rule.append("x")
+            add_rule("x", "LB21b")
             continue
 
         # LB22
         if j == "IN":
-            rule.append("x")
+            add_rule("x", "LB22")
             continue
 
         # LB23
         if i in ("AL", "HL") and j == "NU":
-            rule.append("x")
+            add_rule("x", "LB23")
             continue
         if i == "NU" and j in ("AL", "HL"):
-            rule.append("x")
+            add_rule("x", "LB23")
             continue
 
         # LB23a
         if i == "PR" and j in ("ID", "EB", "EM"):
-            rule.append("x")
+            add_rule("x", "LB23a")
             continue
         if i in ("ID", "EB", "EM") and j == "PO":
-            rule.append("x")
+            add_rule("x", "LB23a")
             continue
 
         # LB24
         if i in ("PR", "PO") and j in ("AL", "HL"):
-            rule.append("x")
+            add_rule("x", "LB24")
             continue
         if i in ("AL", "HL") and j in ("PR", "PO"):
-            rule.append("x")
+            add_rule("x", "LB24")
             continue
 
         # LB25
         # (PR|PO) ? (OP|HY) ? NU (NU|SY|IS) * (CL|CP) ? (PR|PO) ?
         if i in ("PR", "PO") and j in ("OP_OP30", "OP_EA"):
-            rule.append("LB25_OP")
+            add_rule("LB25_OP", "LB25")
             continue
         if i in ("PR", "PO") and j in ("HY"):
-            rule.append("LB25_HY")
+            add_rule("LB25_HY", "LB25")
             continue
         if i in ("PR", "PO", "OP_OP30", "OP_EA", "HY", "LB25_OP", "LB25_HY") and j in ("NU"):
-            rule.append("x")
+            add_rule("x", "LB25")
             continue
         if i in ("NU", "LB25_NU_IS", "LB25_NU_SY") and j == "NU":
-            rule.append("NU")
+            add_rule("NU", "LB25")
             continue
         if i in ("NU", "LB25_NU_IS", "LB25_NU_SY") and j == "SY":
-            rule.append("LB25_NU_SY")
+            add_rule("LB25_NU_SY", "LB25")
             continue
         if i in ("NU", "LB25_NU_IS", "LB25_NU_SY") and j == "IS":
-            rule.append("LB25_NU_IS")
+            add_rule("LB25_NU_IS", "LB25")
             continue
         if i in ("NU", "LB25_NU_IS", "LB25_NU_SY") and j in ("CL"):
-            rule.append("LB25_NU_CL")
+            add_rule("LB25_NU_CL", "LB25")
             continue
         if i in ("NU", "LB25_NU_IS", "LB25_NU_SY") and j in ("CP"):
-            rule.append("LB25_NU_CP")
+            add_rule("LB25_NU_CP", "LB25")
             continue
         if i in ("NU", "LB25_NU_IS", "LB25_NU_SY", "LB25_NU_CL", "LB25_NU_CP") and j in ("PR", "PO"):
-            rule.append("x")
+            add_rule("x", "LB25")
             continue
 
         # Restore
         if i in ("LB25_OP", "LB25_HY"):
-            rule.append("f") # failed. Previous is break
+            add_rule("f", "LB25") # failed. Previous is break
             continue
 
         if i == "LB25_NU_IS":
@@ -368,60 +382,60 @@ for i in prop_type:
 
         # LB26
         if i == "JL" and j in ("JL", "JV", "H2", "H3"):
-            rule.append("x")
+            add_rule("x", "LB26")
             continue
         if i in ("JV", "H2") and j in ("JV", "JT"):
-            rule.append("x")
+            add_rule("x", "LB26")
             continue
         if i in ("JT", "H3") and j == "JT":
-            rule.append("x")
+            add_rule("x", "LB26")
             continue
 
         # LB27
         if i in ("JL", "JV", "JT", "H2", "H3") and j == "IN":
-            rule.append("x")
+            add_rule("x", "LB27")
             continue
         if i in ("JL", "JV", "JT", "H2", "H3") and j == "PO":
-            rule.append("x")
+            add_rule("x", "LB27")
             continue
         if i == "PR" and j in ("JL", "JV", "JT", "H2", "H3"):
-            rule.append("x")
+            add_rule("x", "LB27")
             continue
 
         # LB28
         if i in ("AL", "HL") and j in ("AL", "HL"):
-            rule.append("x")
+            add_rule("x", "LB28")
             continue
 
         # LB29
         if i in ("IS") and j in ("AL", "HL"):
-            rule.append("x")
+            add_rule("x", "LB29")
             continue
 
         # XXX LB30
         if i in ("AL", "HL", "NU") and j == "OP_OP30":
-            rule.append("x")
+            add_rule("x", "LB30")
             continue
         if i == "CP" and j in ("AL", "HL", "NU"):
-            rule.append("x")
+            add_rule("x", "LB30")
             continue
 
         # LB30a
         if i == "RI" and j == "RI":
-            rule.append("RI_RI")
+            add_rule("RI_RI", "LB30a")
             continue
         if i == "RI_RI" and j == "RI":
-            rule.append("/")
+            add_rule("/", "LB30a")
             continue
         if i == "RI_RI":
             i = "RI"
 
         # LB30b
         if i == "EB" and j == "EM":
-            rule.append("x")
+            add_rule("x", "LB30b")
             continue
 
-        rule.append("/")
+        add_rule("/", "LB31")
 
 with open('lb_define.rs', 'w') as prop_file:
     #prop_type = sorted([x for x in set(prop)])
@@ -440,6 +454,11 @@ with open('lb_define.rs', 'w') as prop_file:
     prop_file.write("pub const KEEP_RULE: i8 = -1;\n")
     prop_file.write("\n")
 
+    prop_file.write("pub const PROPERTY_NAMES: [&str; PROP_COUNT] = [\n")
+    for i in prop_type:
+        prop_file.write("  \"%s\",\n" % i)
+    prop_file.write("];\n")
+
 # For Line break property
 with open('properties_defines.rs', 'w') as properties_file:
     properties_file.write("use crate::lb_define::*;\n\n")
@@ -514,6 +533,21 @@ with open('rule_table.rs', 'w') as table_file:
                 table_file.write("\n// %s\n" % prop_type[line])
             except:
                 pass
+    table_file.write("];\n\n")
+
+    # Which rule decided each entry of UAX14_RULE_TABLE, as an index of UAX14_RULE_NAMES.
+    table_file.write("pub const UAX14_RULE_ID_TABLE: [[u8; %d]; %d] = [\n" % (len(prop_type), len(prop_type)))
+    for line in range(len(prop_type)):
+        table_file.write("// %s\n" % prop_type[line])
+        table_file.write("[")
+        for i in rule_id[line * len(prop_type):(line + 1) * len(prop_type)]:
+            table_file.write(" %d," % i)
+        table_file.write("],\n")
+    table_file.write("];\n\n")
+
+    table_file.write("pub const UAX14_RULE_NAMES: [&str; %d] = [\n" % len(rule_names))
+    for i in rule_names:
+        table_file.write("  \"%s\",\n" % i)
     table_file.write("];\n")
 
 